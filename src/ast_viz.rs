@@ -154,7 +154,7 @@ impl DiGraph {
                     let mut subg = DiGraph::empty();
                     subg.name = Some("Params".to_string());
 
-                    for (ptype, name) in params {
+                    for (ptype, name, _) in params {
                         let node_type = Node::new_symbol(&ptype.to_string());
                         let node_name = Node::new_symbol(name);
 
@@ -174,7 +174,62 @@ impl DiGraph {
                 self.add_cont(Content::Edge(Edge::new(self, &node)));
                 self.add_cont(Content::Node(node));
             }
-            ASTInfo::VariableDec(vtype, name) => {
+            ASTInfo::FunctionProto(ftype, name, params) => {
+                self.name = Some("FunctionProto".to_string());
+                let node_type = Node::new_symbol(&ftype.to_string());
+                let node_name = Node::new_symbol(name);
+
+                self.add_cont(Content::Edge(Edge::new(self, &node_type)));
+                self.add_cont(Content::Edge(Edge::new(self, &node_name)));
+                self.add_cont(Content::Node(node_type));
+                self.add_cont(Content::Node(node_name));
+
+                if !params.is_empty() {
+                    let mut subg = DiGraph::empty();
+                    subg.name = Some("Params".to_string());
+
+                    for (ptype, name, _) in params {
+                        let node_type = Node::new_symbol(&ptype.to_string());
+                        let node_name = Node::new_symbol(name);
+
+                        subg.add_cont(Content::Edge(Edge::new(&subg, &node_type)));
+                        subg.add_cont(Content::Edge(Edge::new(&subg, &node_name)));
+                        subg.add_cont(Content::Node(node_type));
+                        subg.add_cont(Content::Node(node_name));
+                    }
+
+                    let node = Node::new_subg(subg);
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
+            }
+            ASTInfo::StructDec(name, fields) => {
+                self.name = Some("StructDec".to_string());
+                let node_name = Node::new_symbol(name);
+
+                self.add_cont(Content::Edge(Edge::new(self, &node_name)));
+                self.add_cont(Content::Node(node_name));
+
+                if !fields.is_empty() {
+                    let mut subg = DiGraph::empty();
+                    subg.name = Some("Fields".to_string());
+
+                    for (ftype, name, _) in fields {
+                        let node_type = Node::new_symbol(&ftype.to_string());
+                        let node_name = Node::new_symbol(name);
+
+                        subg.add_cont(Content::Edge(Edge::new(&subg, &node_type)));
+                        subg.add_cont(Content::Edge(Edge::new(&subg, &node_name)));
+                        subg.add_cont(Content::Node(node_type));
+                        subg.add_cont(Content::Node(node_name));
+                    }
+
+                    let node = Node::new_subg(subg);
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
+            }
+            ASTInfo::VariableDec(vtype, name, init) => {
                 self.name = Some("VariableDec".to_string());
                 let node_type = Node::new_symbol(&vtype.to_string());
                 let node_name = Node::new_symbol(name);
@@ -183,6 +238,12 @@ impl DiGraph {
                 self.add_cont(Content::Edge(Edge::new(self, &node_name)));
                 self.add_cont(Content::Node(node_type));
                 self.add_cont(Content::Node(node_name));
+
+                if let Some(init) = init {
+                    let node = Node::new_subg(DiGraph::from_ast(init));
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
             }
             ASTInfo::BlockStmt(ast1, ast2) => {
                 self.name = Some("BlockStmt".to_string());
@@ -242,6 +303,89 @@ impl DiGraph {
                 self.add_cont(Content::Node(cond_node));
                 self.add_cont(Content::Node(expr_node));
             }
+            ASTInfo::DoWhileStmt(box ast1, box ast2) => {
+                self.name = Some("DoWhileStmt".to_string());
+
+                let do_node = Node::new_symbol("do");
+                let expr_node = Node::new_subg(DiGraph::from_ast(ast1));
+                let while_node = Node::new_symbol("while");
+                let cond_node = Node::new_subg(DiGraph::from_ast(ast2));
+
+                self.add_cont(Content::Edge(Edge::new(self, &do_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &expr_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &while_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &cond_node)));
+                self.add_cont(Content::Node(do_node));
+                self.add_cont(Content::Node(expr_node));
+                self.add_cont(Content::Node(while_node));
+                self.add_cont(Content::Node(cond_node));
+            }
+            ASTInfo::ForStmt(init, cond, step, box ast) => {
+                self.name = Some("ForStmt".to_string());
+
+                let for_node = Node::new_symbol("for");
+
+                self.add_cont(Content::Edge(Edge::new(self, &for_node)));
+                self.add_cont(Content::Node(for_node));
+
+                // for (init; cond; step), any of them can be omitted
+                for (name, clause) in [("Init", init), ("Cond", cond), ("Step", step)] {
+                    if let Some(box ast) = clause {
+                        let mut subg = DiGraph::empty();
+                        subg.name = Some(name.to_string());
+
+                        let clause_node = Node::new_subg(DiGraph::from_ast(ast));
+
+                        subg.add_cont(Content::Edge(Edge::new(&subg, &clause_node)));
+                        subg.add_cont(Content::Node(clause_node));
+
+                        let node = Node::new_subg(subg);
+                        self.add_cont(Content::Edge(Edge::new(self, &node)));
+                        self.add_cont(Content::Node(node));
+                    }
+                }
+
+                let expr_node = Node::new_subg(DiGraph::from_ast(ast));
+
+                self.add_cont(Content::Edge(Edge::new(self, &expr_node)));
+                self.add_cont(Content::Node(expr_node));
+            }
+            ASTInfo::SwitchStmt(box cond, cases) => {
+                self.name = Some("SwitchStmt".to_string());
+
+                let switch_node = Node::new_symbol("switch");
+                let cond_node = Node::new_subg(DiGraph::from_ast(cond));
+
+                self.add_cont(Content::Edge(Edge::new(self, &switch_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &cond_node)));
+                self.add_cont(Content::Node(switch_node));
+                self.add_cont(Content::Node(cond_node));
+
+                for ast in cases {
+                    let node = Node::new_subg(DiGraph::from_ast(ast));
+
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
+            }
+            ASTInfo::CaseStmt(label, statements) => {
+                self.name = Some("CaseStmt".to_string());
+
+                let label_node = match label {
+                    Some(box ast) => Node::new_subg(DiGraph::from_ast(ast)),
+                    None => Node::new_symbol("default"),
+                };
+
+                self.add_cont(Content::Edge(Edge::new(self, &label_node)));
+                self.add_cont(Content::Node(label_node));
+
+                for ast in statements {
+                    let node = Node::new_subg(DiGraph::from_ast(ast));
+
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
+            }
             ASTInfo::ReturnStmt(ast) => {
                 self.name = Some("ReturnStmt".to_string());
 
@@ -257,7 +401,23 @@ impl DiGraph {
                     self.add_cont(Content::Node(retval_node));
                 }
             }
-            ASTInfo::AssignmentExpr(box ast1, box ast2) => {
+            ASTInfo::BreakStmt => {
+                self.name = Some("BreakStmt".to_string());
+
+                let break_node = Node::new_symbol("break");
+
+                self.add_cont(Content::Edge(Edge::new(self, &break_node)));
+                self.add_cont(Content::Node(break_node));
+            }
+            ASTInfo::ContinueStmt => {
+                self.name = Some("ContinueStmt".to_string());
+
+                let continue_node = Node::new_symbol("continue");
+
+                self.add_cont(Content::Edge(Edge::new(self, &continue_node)));
+                self.add_cont(Content::Node(continue_node));
+            }
+            ASTInfo::AssignmentExpr(op, box ast1, box ast2) => {
                 self.name = Some("AssignmentExpr".to_string());
 
                 let var_node = Node::new_subg(DiGraph::from_ast(ast1));
@@ -265,12 +425,29 @@ impl DiGraph {
                 let expr_node = Node::new_subg(DiGraph::from_ast(ast2));
 
                 self.add_cont(Content::Edge(Edge::new(self, &var_node)));
+                self.add_cont(Content::Node(var_node));
+                // Compound assignment, e.g. `+=`
+                if let Some(op) = op {
+                    let op_node = Node::new_symbol(&op.to_string());
+
+                    self.add_cont(Content::Edge(Edge::new(self, &op_node)));
+                    self.add_cont(Content::Node(op_node));
+                }
                 self.add_cont(Content::Edge(Edge::new(self, &equal_node)));
                 self.add_cont(Content::Edge(Edge::new(self, &expr_node)));
-                self.add_cont(Content::Node(var_node));
                 self.add_cont(Content::Node(equal_node));
                 self.add_cont(Content::Node(expr_node));
             }
+            ASTInfo::UnaryExpr(oprand, box ast) => {
+                self.name = Some("UnaryExpr".to_string());
+                let op_node = Node::new_symbol(&oprand.to_string());
+                let val = Node::new_subg(DiGraph::from_ast(ast));
+
+                self.add_cont(Content::Edge(Edge::new(self, &op_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &val)));
+                self.add_cont(Content::Node(op_node));
+                self.add_cont(Content::Node(val));
+            }
             ASTInfo::BinaryExpr(oprand, box ast1, box ast2) => {
                 self.name = Some("BinaryExpr".to_string());
                 let op_node = Node::new_symbol(&oprand.to_string());
@@ -284,6 +461,19 @@ impl DiGraph {
                 self.add_cont(Content::Node(lval));
                 self.add_cont(Content::Node(rval));
             }
+            ASTInfo::ConditionalExpr(box cond, box ast1, box ast2) => {
+                self.name = Some("ConditionalExpr".to_string());
+                let cond_node = Node::new_subg(DiGraph::from_ast(cond));
+                let then_node = Node::new_subg(DiGraph::from_ast(ast1));
+                let else_node = Node::new_subg(DiGraph::from_ast(ast2));
+
+                self.add_cont(Content::Edge(Edge::new(self, &cond_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &then_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &else_node)));
+                self.add_cont(Content::Node(cond_node));
+                self.add_cont(Content::Node(then_node));
+                self.add_cont(Content::Node(else_node));
+            }
             ASTInfo::CallExpr(name, params) => {
                 self.name = Some("CallExpr".to_string());
 
@@ -317,7 +507,33 @@ impl DiGraph {
                 self.add_cont(Content::Node(name_node));
 
                 // Array index
-                if let Some(ast) = ast {
+                for ast in ast {
+                    let mut subg = DiGraph::empty();
+                    subg.name = Some("Index".to_string());
+
+                    let index_node = Node::new_subg(DiGraph::from_ast(ast));
+
+                    subg.add_cont(Content::Edge(Edge::new(&subg, &index_node)));
+                    subg.add_cont(Content::Node(index_node));
+
+                    let node = Node::new_subg(subg);
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
+            }
+            ASTInfo::MemberExpr(base, member, ast) => {
+                self.name = Some("MemberExpr".to_string());
+
+                let base_node = Node::new_subg(DiGraph::from_ast(base));
+                let member_node = Node::new_symbol(member);
+
+                self.add_cont(Content::Edge(Edge::new(self, &base_node)));
+                self.add_cont(Content::Edge(Edge::new(self, &member_node)));
+                self.add_cont(Content::Node(base_node));
+                self.add_cont(Content::Node(member_node));
+
+                // Array index
+                for ast in ast {
                     let mut subg = DiGraph::empty();
                     subg.name = Some("Index".to_string());
 
@@ -339,6 +555,34 @@ impl DiGraph {
                 self.add_cont(Content::Edge(Edge::new(self, &int_node)));
                 self.add_cont(Content::Node(int_node));
             }
+            ASTInfo::InitList(elements) => {
+                self.name = Some("InitList".to_string());
+
+                for ast in elements {
+                    let node = Node::new_subg(DiGraph::from_ast(ast));
+
+                    self.add_cont(Content::Edge(Edge::new(self, &node)));
+                    self.add_cont(Content::Node(node));
+                }
+            }
+            ASTInfo::CharLiteral(val) => {
+                self.name = Some("CharLiteral".to_string());
+
+                let char_node = Node::new_symbol(&format!("'{}'", (*val as char).escape_default()));
+
+                self.add_cont(Content::Edge(Edge::new(self, &char_node)));
+                self.add_cont(Content::Node(char_node));
+            }
+            ASTInfo::StringLiteral(val) => {
+                self.name = Some("StringLiteral".to_string());
+
+                let string = String::from_utf8_lossy(val);
+                // 标签本身写在双引号中, 外层的引号也需要转义
+                let string_node = Node::new_symbol(&format!("\\\"{}\\\"", string.escape_default()));
+
+                self.add_cont(Content::Edge(Edge::new(self, &string_node)));
+                self.add_cont(Content::Node(string_node));
+            }
         }
     }
 
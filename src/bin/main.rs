@@ -4,12 +4,13 @@
 #![feature(is_some_with)]
 
 use clap::Parser;
+use std::collections::HashSet;
 use std::fs;
 use std::io::Write;
 use std::process;
 use std::{fs::File, path::Path};
 
-use cmm::{Ast, CodeBuilder, Context, DiGraph};
+use cmm::{analyze, Ast, CodeBuilder, Context, DiGraph, WarningType};
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -31,6 +32,32 @@ struct Args {
     /// Generate llvm-ir
     #[clap(long)]
     llvmir: bool,
+    /// Only check the source code without generating any file
+    #[clap(long)]
+    check: bool,
+    /// Enable or disable a warning, e.g. `-Wunused-function`, `-Wno-unused-variable`.
+    /// `-Werror` turns warnings into errors
+    #[clap(short = 'W', value_name = "WARNING", parse(try_from_str = parse_warning_flag))]
+    warnings: Vec<WarningFlag>,
+}
+
+#[derive(Debug)]
+enum WarningFlag {
+    Enable(WarningType),
+    Disable(WarningType),
+    Error,
+    NoError,
+}
+
+fn parse_warning_flag(flag: &str) -> Result<WarningFlag, String> {
+    match flag {
+        "error" => Ok(WarningFlag::Error),
+        "no-error" => Ok(WarningFlag::NoError),
+        _ => match flag.strip_prefix("no-") {
+            Some(name) => Ok(WarningFlag::Disable(name.parse()?)),
+            None => Ok(WarningFlag::Enable(flag.parse()?)),
+        },
+    }
 }
 
 fn main() {
@@ -48,50 +75,92 @@ fn main() {
         }
     };
 
-    let context = Context::create();
-    match Ast::parse(source_code) {
-        Ok(ast) => match CodeBuilder::new(&context, args.file.as_str(), &ast, args.opt) {
-            Ok(codebuilder) => {
-                match (args.asm, args.llvmir) {
-                    (true, _) => codebuilder.build_asm(Path::new(&filename)),
-                    (false, true) => codebuilder.build_llvmir(Path::new(&filename)),
-                    (false, false) => {
-                        let tmpfile = format!("{}.s", filename);
-                        let io_c = if fs::try_exists("/usr/lib/cmm/io.c").is_ok_and(|b| *b) {
-                            "/usr/lib/cmm/io.c"
-                        } else if fs::try_exists("./io.c").is_ok_and(|b| *b) {
-                            "./io.c"
-                        } else {
-                            eprintln!("Cannot find io.c in /usr/lib/cmm or current directory");
-                            process::exit(1);
-                        };
-                        codebuilder.build_asm(Path::new(&tmpfile));
-                        process::Command::new("clang")
-                            .args([tmpfile.as_str(), io_c, "-o", filename.as_str()])
-                            .spawn()
-                            .expect("Fail to start clang")
-                            .wait()
-                            .expect("Fail to link io.c with clang");
-                        fs::remove_file(Path::new(&tmpfile)).expect("Fail to remove temp file");
-                    }
-                };
-
-                // Generate dot file
-                if let Some(dotfile) = args.dotfile {
-                    let dot_cont = DiGraph::new(&args.file, &ast).write_dot();
-                    let mut file = File::create(&dotfile).expect("Unable to create a dot file!");
-                    file.write_all(dot_cont.as_bytes())
-                        .expect("Unable to write dot file!");
-                }
-            }
-            Err(e) => {
-                eprintln!("Error: {}", e);
-                process::exit(1);
-            }
-        },
+    let ast = match Ast::parse(source_code) {
+        Ok(ast) => ast,
         Err(e) => {
             eprintln!("Error: {}", e);
+            eprintln!("1 error(s) generated.");
             process::exit(1);
         }
+    };
+    // 报告所有的语义错误, 而不是只报告第一个
+    let semantics = match analyze(&ast) {
+        Ok(semantics) => semantics,
+        Err(errors) => {
+            for e in &errors {
+                eprintln!("Error: {}", e);
+            }
+            eprintln!("{} error(s) generated.", errors.len());
+            process::exit(1);
+        }
+    };
+
+    // 所有警告默认开启, 后面的选项覆盖前面的
+    let mut disabled = HashSet::new();
+    let mut warnings_as_errors = false;
+    for flag in &args.warnings {
+        match flag {
+            WarningFlag::Enable(warning) => {
+                disabled.remove(warning);
+            }
+            WarningFlag::Disable(warning) => {
+                disabled.insert(*warning);
+            }
+            WarningFlag::Error => warnings_as_errors = true,
+            WarningFlag::NoError => warnings_as_errors = false,
+        }
+    }
+    let warnings: Vec<_> = semantics
+        .warnings()
+        .iter()
+        .filter(|w| !disabled.contains(&w.warning_type()))
+        .collect();
+    for w in &warnings {
+        if warnings_as_errors {
+            eprintln!("Error: {}", w);
+        } else {
+            eprintln!("Warning: {}", w);
+        }
+    }
+    if warnings_as_errors && !warnings.is_empty() {
+        eprintln!("{} error(s) generated.", warnings.len());
+        process::exit(1);
+    }
+    if args.check {
+        return;
+    }
+
+    let context = Context::create();
+    let codebuilder = CodeBuilder::new(&context, args.file.as_str(), &ast, semantics, args.opt);
+    match (args.asm, args.llvmir) {
+        (true, _) => codebuilder.build_asm(Path::new(&filename)),
+        (false, true) => codebuilder.build_llvmir(Path::new(&filename)),
+        (false, false) => {
+            let tmpfile = format!("{}.s", filename);
+            let io_c = if fs::try_exists("/usr/lib/cmm/io.c").is_ok_and(|b| *b) {
+                "/usr/lib/cmm/io.c"
+            } else if fs::try_exists("./io.c").is_ok_and(|b| *b) {
+                "./io.c"
+            } else {
+                eprintln!("Cannot find io.c in /usr/lib/cmm or current directory");
+                process::exit(1);
+            };
+            codebuilder.build_asm(Path::new(&tmpfile));
+            process::Command::new("clang")
+                .args([tmpfile.as_str(), io_c, "-o", filename.as_str()])
+                .spawn()
+                .expect("Fail to start clang")
+                .wait()
+                .expect("Fail to link io.c with clang");
+            fs::remove_file(Path::new(&tmpfile)).expect("Fail to remove temp file");
+        }
+    };
+
+    // Generate dot file
+    if let Some(dotfile) = args.dotfile {
+        let dot_cont = DiGraph::new(&args.file, &ast).write_dot();
+        let mut file = File::create(&dotfile).expect("Unable to create a dot file!");
+        file.write_all(dot_cont.as_bytes())
+            .expect("Unable to write dot file!");
     }
 }
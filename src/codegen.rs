@@ -1,17 +1,24 @@
-use crate::error::{Error, ErrorType, Result};
-use crate::parser::{ASTInfo, Ast, Operand, Type};
+use crate::parser::{ASTInfo, Ast, Field, Operand, Param, Type, UnaryOp};
+use crate::sema::Semantics;
 use either::Either;
 use inkwell::{
+    basic_block::BasicBlock,
     builder::Builder,
     context::Context,
     module::{Linkage, Module},
     passes::PassManager,
     targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine},
-    types::{BasicMetadataTypeEnum, BasicType},
-    values::{BasicValue, BasicValueEnum, FunctionValue, PointerValue},
-    IntPredicate, OptimizationLevel,
+    types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum, FunctionType, StructType},
+    values::{
+        BasicMetadataValueEnum, BasicValue, BasicValueEnum, FunctionValue, IntValue, PointerValue,
+    },
+    AddressSpace, IntPredicate, OptimizationLevel,
+};
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+    path::Path,
 };
-use std::{borrow::Borrow, collections::HashMap, path::Path};
 
 pub struct CodeBuilder<'ctx> {
     /// A Context is a container for all LLVM entities including Modules.
@@ -24,21 +31,40 @@ pub struct CodeBuilder<'ctx> {
     /// them into a basic block: either at the end of a BasicBlock,
     /// or at a specific iterator location in a block.
     builder: Builder<'ctx>,
+    /// Types of expressions computed by semantic analysis.
+    /// The program has been checked before generating code, so it's never rejected here.
+    semantics: Semantics,
 
     /// Global variables. Map variables' name to it's type and pointer.
     global_variables: HashMap<String, (Type, PointerValue<'ctx>)>,
-    /// Global functions.  Map functions' name to it's type and pointer.
-    global_functions: HashMap<String, (Type, FunctionValue<'ctx>)>,
+    /// Struct definitions. Map structs' name to it's llvm type and fields.
+    struct_types: HashMap<String, (StructType<'ctx>, Vec<(Type, String)>)>,
+    /// Global functions.  Map functions' name to it's type, parameters' type and pointer.
+    global_functions: HashMap<String, (Type, Vec<Type>, FunctionValue<'ctx>)>,
+    /// Functions declared by prototypes but not defined yet.
+    /// The ones never defined are left as external declarations for the linker.
+    prototypes: HashSet<String>,
     /// Local variables. It represents the nesting of scopes.
     variables_stack: Vec<HashMap<String, (Type, PointerValue<'ctx>)>>,
     /// The function that code builder is generating.
     current_function: Option<(Type, FunctionValue<'ctx>)>,
+    /// Loops and switches that code builder is generating. It represents the nesting of them,
+    /// each is the block to jump to for `continue` and the block for `break`.
+    /// A switch has no block for `continue`, which goes to the enclosing loop.
+    loop_stack: Vec<(Option<BasicBlock<'ctx>>, BasicBlock<'ctx>)>,
     /// For optimize
     fpm: Option<PassManager<FunctionValue<'ctx>>>,
 }
 
 impl<'ctx> CodeBuilder<'ctx> {
-    pub fn new<T>(context: &'ctx Context, name: T, ast: &Vec<Ast>, opt: bool) -> Result<Self>
+    /// The program must have passed the semantic analysis which gives `semantics`.
+    pub fn new<T>(
+        context: &'ctx Context,
+        name: T,
+        ast: &Vec<Ast>,
+        semantics: Semantics,
+        opt: bool,
+    ) -> Self
     where
         T: Borrow<str>,
     {
@@ -64,15 +90,19 @@ impl<'ctx> CodeBuilder<'ctx> {
             context,
             module,
             builder,
+            semantics,
             global_variables: HashMap::new(),
+            struct_types: HashMap::new(),
             variables_stack: Vec::new(),
             global_functions: HashMap::new(),
+            prototypes: HashSet::new(),
             current_function: None,
+            loop_stack: Vec::new(),
             fpm,
         };
 
-        codegen.generate(ast)?;
-        Ok(codegen)
+        codegen.generate(ast);
+        codegen
     }
 
     /// Build llvm-ir assembly file
@@ -107,13 +137,13 @@ impl<'ctx> CodeBuilder<'ctx> {
             .unwrap();
     }
 
-    fn generate(&mut self, ast: &Vec<Ast>) -> Result<()> {
+    fn generate(&mut self, ast: &Vec<Ast>) {
         let input = self.context.i32_type().fn_type(&[], false);
         let input = self
             .module
             .add_function("input", input, Some(Linkage::External));
         self.global_functions
-            .insert("input".to_string(), (Type::Int, input));
+            .insert("input".to_string(), (Type::Int, vec![], input));
 
         let output = self
             .context
@@ -123,71 +153,154 @@ impl<'ctx> CodeBuilder<'ctx> {
             .module
             .add_function("output", output, Some(Linkage::External));
         self.global_functions
-            .insert("output".to_string(), (Type::Void, output));
+            .insert("output".to_string(), (Type::Void, vec![Type::Int], output));
+
+        let string_type = Type::Ptr(Box::new(Type::Char));
+        let print_string = self
+            .context
+            .void_type()
+            .fn_type(&[self.to_llvm_basic_metadata_type(&string_type)], false);
+        let print_string =
+            self.module
+                .add_function("print_string", print_string, Some(Linkage::External));
+        self.global_functions.insert(
+            "print_string".to_string(),
+            (Type::Void, vec![string_type], print_string),
+        );
 
         for i in ast {
             match &i.info {
                 ASTInfo::FunctionDec(type_, name, params, body) => {
-                    self.gen_function(i.position, type_, name, params, body)?
+                    self.gen_function(type_, name, params, body)
+                }
+                ASTInfo::FunctionProto(type_, name, params) => {
+                    self.gen_function_proto(type_, name, params)
                 }
-                ASTInfo::VariableDec(type_, name) => {
-                    self.gen_global_variable(i.position, type_, name)?
+                ASTInfo::StructDec(name, fields) => self.gen_struct(name, fields),
+                ASTInfo::VariableDec(type_, name, init) => {
+                    self.gen_global_variable(type_, name, init.as_deref())
                 }
                 _ => panic!(),
             }
         }
-        Ok(())
     }
 
-    fn gen_global_variable(
-        &mut self,
-        position: (usize, usize),
-        type_: &Type,
-        name: &str,
-    ) -> Result<()> {
-        if self.global_variables.contains_key(name) || self.global_functions.contains_key(name) {
-            Err(Error::new(position, ErrorType::VariableRedefinition))?
-        }
+    fn gen_struct(&mut self, name: &str, fields: &[Field]) {
+        // 先声明结构体, 成员可以是指向自身的指针, 例如链表的结点
+        let struct_type = self.context.opaque_struct_type(name);
+        self.struct_types
+            .insert(name.to_string(), (struct_type, vec![]));
+
+        let field_types: Vec<BasicTypeEnum> = fields
+            .iter()
+            .map(|(type_, _, _)| self.to_llvm_basic_type(type_))
+            .collect();
+        struct_type.set_body(&field_types, false);
+        self.struct_types.get_mut(name).unwrap().1 = fields
+            .iter()
+            .map(|(type_, field, _)| (type_.clone(), field.clone()))
+            .collect();
+    }
+
+    fn gen_global_variable(&mut self, type_: &Type, name: &str, init: Option<&Ast>) {
+        let initializer = match init {
+            Some(init) => self.gen_const_initializer(type_, init),
+            None => self.to_llvm_const_zero(type_),
+        };
         let v = self
             .module
-            .add_global(type_.to_llvm_basic_type(self.context), None, name);
-        v.set_initializer(&self.context.i32_type().const_int(0, false));
+            .add_global(self.to_llvm_basic_type(type_), None, name);
+        v.set_initializer(&initializer);
         self.global_variables
-            .insert(name.to_string(), (*type_, v.as_pointer_value()));
-        Ok(())
+            .insert(name.to_string(), (type_.clone(), v.as_pointer_value()));
     }
 
-    fn gen_function(
-        &mut self,
-        position: (usize, usize),
-        type_: &Type,
-        name: &str,
-        params: &[(Type, String)],
-        body: &Ast,
-    ) -> Result<()> {
-        if self.global_variables.contains_key(name) || self.global_functions.contains_key(name) {
-            Err(Error::new(position, ErrorType::FunctionRedefinition))?
+    /// The initial value of a global variable, which must be known at compile time.
+    /// Elements that are not in the initializer list are filled with zero.
+    fn gen_const_initializer(&self, type_: &Type, init: &Ast) -> BasicValueEnum<'ctx> {
+        match (type_, &init.info) {
+            (Type::Array(elem_type, size), ASTInfo::InitList(elements)) => {
+                debug_assert!(elements.len() <= *size);
+                let mut values: Vec<BasicValueEnum> = elements
+                    .iter()
+                    .map(|element| self.gen_const_initializer(elem_type, element))
+                    .collect();
+                while values.len() < *size {
+                    values.push(self.to_llvm_const_zero(elem_type));
+                }
+                let value = match self.to_llvm_basic_type(elem_type) {
+                    BasicTypeEnum::IntType(t) => t.const_array(
+                        &values
+                            .into_iter()
+                            .map(|v| v.into_int_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::ArrayType(t) => t.const_array(
+                        &values
+                            .into_iter()
+                            .map(|v| v.into_array_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::PointerType(t) => t.const_array(
+                        &values
+                            .into_iter()
+                            .map(|v| v.into_pointer_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    BasicTypeEnum::StructType(t) => t.const_array(
+                        &values
+                            .into_iter()
+                            .map(|v| v.into_struct_value())
+                            .collect::<Vec<_>>(),
+                    ),
+                    _ => unreachable!(),
+                };
+                value.as_basic_value_enum()
+            }
+            (Type::Struct(name), ASTInfo::InitList(elements)) => {
+                let (struct_type, fields) = &self.struct_types[name];
+                debug_assert!(elements.len() <= fields.len());
+                let values: Vec<BasicValueEnum> = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (field_type, _))| match elements.get(index) {
+                        Some(element) => self.gen_const_initializer(field_type, element),
+                        None => self.to_llvm_const_zero(field_type),
+                    })
+                    .collect();
+                struct_type
+                    .const_named_struct(&values)
+                    .as_basic_value_enum()
+            }
+            // 语义分析保证了指针的初始值是空指针常量, 其他的初始值都是整数常量
+            (Type::Ptr(_), _) => self.to_llvm_const_zero(type_),
+            _ => self
+                .to_llvm_basic_type(type_)
+                .into_int_type()
+                .const_int(init.eval_const().unwrap() as u64, true)
+                .as_basic_value_enum(),
         }
+    }
 
-        let param_types: Vec<BasicMetadataTypeEnum<'ctx>> = params
-            .iter()
-            .map(|(param_type, _)| param_type.to_llvm_basic_metadata_type(self.context))
-            .collect();
-        let ty = match type_ {
-            Type::Void => self.context.void_type().fn_type(&param_types[..], false),
-            other => other
-                .to_llvm_basic_type(self.context)
-                .fn_type(&param_types[..], false),
+    fn gen_function(&mut self, type_: &Type, name: &str, params: &[Param], body: &Ast) {
+        let param_types: Vec<Type> = params.iter().map(|(type_, _, _)| type_.clone()).collect();
+        let function = if self.prototypes.remove(name) {
+            // 之前声明过原型, 定义使用原型声明的函数
+            let (return_type, proto_types, function) = &self.global_functions[name];
+            debug_assert!(return_type == type_ && *proto_types == param_types);
+            *function
+        } else {
+            let ty = self.gen_function_type(type_, params);
+            let function = self.module.add_function(name, ty, None);
+            self.global_functions
+                .insert(name.to_string(), (type_.clone(), param_types, function));
+            function
         };
-
-        let function = self.module.add_function(name, ty, None);
-        self.global_functions
-            .insert(name.to_string(), (*type_, function));
         let basic_block = self.context.append_basic_block(function, "entry");
 
         let mut p = HashMap::new();
         for (index, arg) in function.get_param_iter().enumerate() {
-            let (arg_type, arg_name) = &params[index];
+            let (arg_type, arg_name, _) = &params[index];
             arg.set_name(arg_name);
 
             self.builder.position_at_end(basic_block);
@@ -195,19 +308,24 @@ impl<'ctx> CodeBuilder<'ctx> {
             // alloc variable on stack
             let ptr = self
                 .builder
-                .build_alloca(arg_type.to_llvm_basic_type(self.context), "");
+                .build_alloca(self.to_llvm_basic_type(arg_type), "");
             self.builder
                 .build_store(ptr, function.get_nth_param(index as u32).unwrap());
 
-            p.insert(arg_name.clone(), (*arg_type, ptr));
+            p.insert(arg_name.clone(), (arg_type.clone(), ptr));
         }
         self.variables_stack.push(p);
-        self.current_function = Some((*type_, function));
+        self.current_function = Some((type_.clone(), function));
 
         self.builder.position_at_end(basic_block);
-        self.gen_block_stmt(body)?;
+        self.gen_block_stmt(body);
+        // 语义分析保证了非void函数不会执行到结尾
         if self.no_terminator() {
-            self.builder.build_return(None);
+            if *type_ == Type::Void {
+                self.builder.build_return(None);
+            } else {
+                self.builder.build_unreachable();
+            }
         }
 
         self.variables_stack.pop();
@@ -216,74 +334,173 @@ impl<'ctx> CodeBuilder<'ctx> {
         if let Some(fpm) = &self.fpm {
             fpm.run_on(&function);
         }
-        Ok(())
     }
 
-    fn gen_block_stmt(&mut self, ast: &Ast) -> Result<()> {
+    /// A prototype declares the function so that it can be called before its definition,
+    /// e.g. mutually recursive functions. Prototypes of the same function must be the same.
+    fn gen_function_proto(&mut self, type_: &Type, name: &str, params: &[Param]) {
+        if self.global_functions.contains_key(name) {
+            return;
+        }
+        let ty = self.gen_function_type(type_, params);
+        let param_types: Vec<Type> = params.iter().map(|(type_, _, _)| type_.clone()).collect();
+        let function = self.module.add_function(name, ty, Some(Linkage::External));
+        self.global_functions
+            .insert(name.to_string(), (type_.clone(), param_types, function));
+        self.prototypes.insert(name.to_string());
+    }
+
+    /// The llvm type of the function, used by both the definition and the prototype.
+    fn gen_function_type(&self, type_: &Type, params: &[Param]) -> FunctionType<'ctx> {
+        let param_types: Vec<BasicMetadataTypeEnum<'ctx>> = params
+            .iter()
+            .map(|(param_type, _, _)| self.to_llvm_basic_metadata_type(param_type))
+            .collect();
+        match type_ {
+            Type::Void => self.context.void_type().fn_type(&param_types[..], false),
+            other => self
+                .to_llvm_basic_type(other)
+                .fn_type(&param_types[..], false),
+        }
+    }
+
+    fn gen_block_stmt(&mut self, ast: &Ast) {
         let info = &ast.info;
 
         if let ASTInfo::BlockStmt(variables, statements) = info {
             self.variables_stack.push(HashMap::new());
             for var in variables {
-                let var_position = var.position;
-                let var_info = &var.info;
-                if let ASTInfo::VariableDec(type_, name) = var_info {
-                    if self.variables_stack.last().unwrap().contains_key(name) {
-                        Err(Error::new(var_position, ErrorType::VariableRedefinition))?
-                    };
+                if let ASTInfo::VariableDec(type_, name, init) = &var.info {
                     let v = self
                         .builder
-                        .build_alloca(type_.to_llvm_basic_type(self.context), name);
-                    // 对于int a[len]这样的声明, 我们转换成int* a进行后续的使用.
-                    if let Type::IntArray(_) = type_ {
-                        let pv = self.builder.build_alloca(
-                            self.context
-                                .i32_type()
-                                .ptr_type(inkwell::AddressSpace::Generic),
-                            name,
-                        );
-                        let value = unsafe {
-                            self.builder.build_in_bounds_gep(
-                                v,
-                                &[
-                                    self.context.i32_type().const_int(0, false),
-                                    self.context.i32_type().const_int(0, false),
-                                ],
-                                name,
-                            )
-                        };
-                        self.builder.build_store(pv, value);
-                        self.variables_stack
-                            .last_mut()
-                            .unwrap()
-                            .insert(name.clone(), (Type::IntPtr, pv));
-                    } else {
-                        self.variables_stack
-                            .last_mut()
-                            .unwrap()
-                            .insert(name.clone(), (*type_, v));
+                        .build_alloca(self.to_llvm_basic_type(type_), name);
+                    self.variables_stack
+                        .last_mut()
+                        .unwrap()
+                        .insert(name.clone(), (type_.clone(), v));
+                    // 初始值在分配空间之后立即写入, 数组中没有初始值的元素为0
+                    if let Some(init) = init {
+                        if let Type::Array(_, _) | Type::Struct(_) = type_ {
+                            self.builder.build_store(v, self.to_llvm_const_zero(type_));
+                        }
+                        self.gen_local_initializer(v, type_, init);
                     }
                 }
             }
 
-            for stmt in statements {
-                self.gen_statement(stmt)?;
-            }
+            self.gen_statements(statements);
             self.variables_stack.pop();
         }
-        Ok(())
     }
 
-    fn gen_statement(&mut self, stmt: &Ast) -> Result<()> {
+    /// Every case has a basic block, which are placed in order so that
+    /// a case without `break` falls through to the next one.
+    fn gen_switch_stmt(&mut self, cond: &Ast, cases: &[Ast]) {
+        let value = self.gen_expression(cond);
+        let value = self.gen_promotion(self.semantics.type_of(cond), value);
+
+        let current_block = self.builder.get_insert_block().unwrap();
+        let destination_block = self
+            .context
+            .insert_basic_block_after(current_block, "switch_dest_block");
+
+        let mut labels: Vec<(i32, BasicBlock)> = vec![];
+        let mut default_block = None;
+        let mut case_blocks = vec![];
+        for case in cases {
+            let case_block = self
+                .context
+                .prepend_basic_block(destination_block, "case_block");
+            match &case.info {
+                ASTInfo::CaseStmt(Some(label), _) => {
+                    labels.push((label.eval_const().unwrap(), case_block));
+                }
+                ASTInfo::CaseStmt(None, _) => default_block = Some(case_block),
+                _ => unreachable!(),
+            }
+            case_blocks.push(case_block);
+        }
+
+        let labels: Vec<(IntValue, BasicBlock)> = labels
+            .into_iter()
+            .map(|(v, block)| (self.context.i32_type().const_int(v as u64, true), block))
+            .collect();
+        // 没有default时, 不匹配任何case则直接跳出switch
+        self.builder.build_switch(
+            value,
+            default_block.unwrap_or(destination_block),
+            &labels[..],
+        );
+
+        self.loop_stack.push((None, destination_block));
+        for (index, case) in cases.iter().enumerate() {
+            self.builder.position_at_end(case_blocks[index]);
+            if let ASTInfo::CaseStmt(_, statements) = &case.info {
+                self.gen_statements(statements);
+            }
+            if self.no_terminator() {
+                let next_block = case_blocks.get(index + 1).unwrap_or(&destination_block);
+                self.builder.build_unconditional_branch(*next_block);
+            }
+        }
+        self.loop_stack.pop();
+
+        self.position_at_destination(destination_block);
+    }
+
+    /// Store the initial value of a local variable to `ptr`,
+    /// an initializer list is stored element by element.
+    fn gen_local_initializer(&self, ptr: PointerValue, type_: &Type, init: &Ast) {
+        match (type_, &init.info) {
+            (Type::Array(elem_type, _), ASTInfo::InitList(elements)) => {
+                for (index, element) in elements.iter().enumerate() {
+                    let elem_ptr = unsafe {
+                        self.builder.build_in_bounds_gep(
+                            ptr,
+                            &[
+                                self.context.i32_type().const_int(0, false),
+                                self.context.i32_type().const_int(index as u64, false),
+                            ],
+                            "",
+                        )
+                    };
+                    self.gen_local_initializer(elem_ptr, elem_type, element);
+                }
+            }
+            (Type::Struct(name), ASTInfo::InitList(elements)) => {
+                let fields = &self.struct_types[name].1;
+                for (index, element) in elements.iter().enumerate() {
+                    let field_ptr = self
+                        .builder
+                        .build_struct_gep(ptr, index as u32, "")
+                        .unwrap();
+                    self.gen_local_initializer(field_ptr, &fields[index].0, element);
+                }
+            }
+            (_, ASTInfo::InitList(_)) => unreachable!(),
+            _ => {
+                let value = self.gen_expression(init);
+                let value = self.gen_implicit_cast(value, type_);
+                self.builder.build_store(ptr, value);
+            }
+        }
+    }
+
+    /// Statements after `return`, `break` or `continue` are unreachable and not generated.
+    fn gen_statements(&mut self, statements: &[Ast]) {
+        for stmt in statements {
+            if !self.no_terminator() {
+                break;
+            }
+            self.gen_statement(stmt);
+        }
+    }
+
+    fn gen_statement(&mut self, stmt: &Ast) {
         match &stmt.info {
-            ASTInfo::BlockStmt(_, _) => self.gen_block_stmt(stmt)?,
+            ASTInfo::BlockStmt(_, _) => self.gen_block_stmt(stmt),
             ASTInfo::SelectionStmt(cond, then_stmt, else_stmt) => {
-                let comparison = self.gen_expression(cond)?.1.into_int_value();
-                let comparison = self.builder.build_int_truncate(
-                    comparison,
-                    self.context.bool_type(),
-                    "condition",
-                );
+                let comparison = self.gen_condition(cond);
                 let current_block = self.builder.get_insert_block().unwrap();
 
                 let then_block = self
@@ -302,13 +519,13 @@ impl<'ctx> CodeBuilder<'ctx> {
                         self.builder
                             .build_conditional_branch(comparison, then_block, else_block);
                         self.builder.position_at_end(then_block);
-                        self.gen_statement(then_stmt)?;
+                        self.gen_statement(then_stmt);
                         if self.no_terminator() {
                             self.builder.build_unconditional_branch(destination_block);
                         }
 
                         self.builder.position_at_end(else_block);
-                        self.gen_statement(else_stmt)?;
+                        self.gen_statement(else_stmt);
                         if self.no_terminator() {
                             self.builder.build_unconditional_branch(destination_block);
                         }
@@ -320,14 +537,14 @@ impl<'ctx> CodeBuilder<'ctx> {
                             destination_block,
                         );
                         self.builder.position_at_end(then_block);
-                        self.gen_statement(then_stmt)?;
+                        self.gen_statement(then_stmt);
                         if self.no_terminator() {
                             self.builder.build_unconditional_branch(destination_block);
                         }
                     }
                 };
 
-                self.builder.position_at_end(destination_block);
+                self.position_at_destination(destination_block);
             }
             ASTInfo::IterationStmt(cond, loop_stmt) => {
                 let current_block = self.builder.get_insert_block().unwrap();
@@ -344,108 +561,273 @@ impl<'ctx> CodeBuilder<'ctx> {
                     .insert_basic_block_after(loop_body, "loop_dest_block");
 
                 self.builder.position_at_end(loop_head);
-                let comparison = self.gen_expression(cond)?.1.into_int_value();
-                let comparison = self.builder.build_int_truncate(
-                    comparison,
-                    self.context.bool_type(),
-                    "condition",
-                );
+                let comparison = self.gen_condition(cond);
                 self.builder
                     .build_conditional_branch(comparison, loop_body, destination_block);
 
                 self.builder.position_at_end(loop_body);
-                self.gen_statement(loop_stmt)?;
-                self.builder.build_unconditional_branch(loop_head);
+                self.loop_stack.push((Some(loop_head), destination_block));
+                self.gen_statement(loop_stmt);
+                self.loop_stack.pop();
+                if self.no_terminator() {
+                    self.builder.build_unconditional_branch(loop_head);
+                }
+
+                self.position_at_destination(destination_block);
+            }
+            ASTInfo::DoWhileStmt(loop_stmt, cond) => {
+                let current_block = self.builder.get_insert_block().unwrap();
+                let do_body = self
+                    .context
+                    .insert_basic_block_after(current_block, "do_body");
+                self.builder.build_unconditional_branch(do_body);
+
+                let do_cond = self.context.insert_basic_block_after(do_body, "do_cond");
+                let destination_block = self
+                    .context
+                    .insert_basic_block_after(do_cond, "do_dest_block");
+
+                // 循环体至少执行一次, 之后才检查条件
+                self.builder.position_at_end(do_body);
+                self.loop_stack.push((Some(do_cond), destination_block));
+                self.gen_statement(loop_stmt);
+                self.loop_stack.pop();
+                if self.no_terminator() {
+                    self.builder.build_unconditional_branch(do_cond);
+                }
+
+                // 循环体不能执行到结尾且没有continue时, 条件是不可达的
+                if do_cond.get_first_use().is_some() {
+                    self.builder.position_at_end(do_cond);
+                    let comparison = self.gen_condition(cond);
+                    self.builder
+                        .build_conditional_branch(comparison, do_body, destination_block);
+                } else {
+                    do_cond.remove_from_function().unwrap();
+                }
+
+                self.position_at_destination(destination_block);
+            }
+            ASTInfo::ForStmt(init, cond, step, loop_stmt) => {
+                if let Some(init) = init {
+                    self.gen_statement(init);
+                }
+                let current_block = self.builder.get_insert_block().unwrap();
+                let for_head = self
+                    .context
+                    .insert_basic_block_after(current_block, "for_head");
+                self.builder.build_unconditional_branch(for_head);
+
+                let for_body = self.context.insert_basic_block_after(for_head, "for_body");
+                let for_step = self.context.insert_basic_block_after(for_body, "for_step");
+                let destination_block = self
+                    .context
+                    .insert_basic_block_after(for_step, "for_dest_block");
+
+                // 没有条件的for循环是死循环
+                self.builder.position_at_end(for_head);
+                match cond {
+                    Some(cond) => {
+                        let comparison = self.gen_condition(cond);
+                        self.builder.build_conditional_branch(
+                            comparison,
+                            for_body,
+                            destination_block,
+                        );
+                    }
+                    None => {
+                        self.builder.build_unconditional_branch(for_body);
+                    }
+                }
 
-                self.builder.position_at_end(destination_block);
+                self.builder.position_at_end(for_body);
+                self.loop_stack.push((Some(for_step), destination_block));
+                self.gen_statement(loop_stmt);
+                self.loop_stack.pop();
+                if self.no_terminator() {
+                    self.builder.build_unconditional_branch(for_step);
+                }
+
+                if for_step.get_first_use().is_some() {
+                    self.builder.position_at_end(for_step);
+                    if let Some(step) = step {
+                        self.gen_statement(step);
+                    }
+                    self.builder.build_unconditional_branch(for_head);
+                } else {
+                    for_step.remove_from_function().unwrap();
+                }
+
+                self.position_at_destination(destination_block);
             }
+            ASTInfo::SwitchStmt(cond, cases) => self.gen_switch_stmt(cond, cases),
             ASTInfo::ReturnStmt(ret_value) => {
-                let func_return_type = self.current_function.unwrap().0;
+                let func_return_type = &self.current_function.as_ref().unwrap().0;
                 match ret_value {
                     Some(ast) => {
-                        let (type_, value) = self.gen_expression(ast)?;
-                        if type_ == func_return_type {
-                            self.builder.build_return(Some(&value));
-                        } else {
-                            Err(Error::new(ast.position, ErrorType::MismatchedTypeFunction))?
-                        }
+                        let value = self.gen_expression(ast);
+                        let value = self.gen_implicit_cast(value, func_return_type);
+                        self.builder.build_return(Some(&value));
                     }
                     None => {
-                        if func_return_type == Type::Void {
-                            self.builder.build_return(None);
-                        } else {
-                            Err(Error::new(stmt.position, ErrorType::MismatchedTypeFunction))?
-                        }
+                        self.builder.build_return(None);
                     }
                 }
             }
-            ASTInfo::AssignmentExpr(var, expr) => {
-                self.gen_assignment_expr(var, expr)?;
+            ASTInfo::BreakStmt => {
+                let (_, loop_exit) = self.loop_stack.last().unwrap();
+                self.builder.build_unconditional_branch(*loop_exit);
+            }
+            ASTInfo::ContinueStmt => {
+                let loop_head = self
+                    .loop_stack
+                    .iter()
+                    .rev()
+                    .find_map(|(head, _)| *head)
+                    .unwrap();
+                self.builder.build_unconditional_branch(loop_head);
+            }
+            ASTInfo::AssignmentExpr(op, var, expr) => {
+                self.gen_assignment_expr(op.as_ref(), var, expr);
+            }
+            ASTInfo::UnaryExpr(op, expr) => {
+                self.gen_unary_expr(op, expr);
             }
             ASTInfo::BinaryExpr(op, lhs, rhs) => {
-                self.gen_binary_expr(op, lhs, rhs)?;
+                self.gen_binary_expr(op, lhs, rhs);
             }
             ASTInfo::CallExpr(name, arguments) => {
-                self.gen_function_call(stmt.position, name, arguments)?;
+                self.gen_function_call(name, arguments);
+            }
+            _ => {
+                self.gen_expression(stmt);
             }
-            _ => unreachable!(),
         }
-        Ok(())
     }
 
-    fn gen_expression(&self, ast: &Ast) -> Result<(Type, BasicValueEnum)> {
+    /// The value of the expression, whose type is given by the semantic analysis.
+    fn gen_expression(&self, ast: &Ast) -> BasicValueEnum {
         match &ast.info {
-            ASTInfo::AssignmentExpr(var, expr) => self.gen_assignment_expr(var, expr),
+            ASTInfo::AssignmentExpr(op, var, expr) => {
+                self.gen_assignment_expr(op.as_ref(), var, expr)
+            }
+            ASTInfo::UnaryExpr(op, expr) => self.gen_unary_expr(op, expr),
             ASTInfo::BinaryExpr(op, lhs, rhs) => self.gen_binary_expr(op, lhs, rhs),
-            ASTInfo::CallExpr(name, arguments) => {
-                // 在expression上下文中不应该返回void
-                let r = self.gen_function_call(ast.position, name, arguments);
-                if r.is_ok() && r.as_ref().unwrap().0 == Type::Void {
-                    Err(Error::new(ast.position, ErrorType::ExpressionVoidType))?
-                }
-                r
-            }
-            ASTInfo::Variable(name, index) => {
-                let (type_, ptr) =
-                    self.gen_variable(ast.position, name, &index.as_ref().map(|x| x.as_ref()))?;
-                let value = self.builder.build_load(ptr, "");
-                Ok((type_, value))
-            }
-            ASTInfo::IntLiteral(value) => Ok((
-                Type::Int,
-                self.context
-                    .i32_type()
-                    .const_int(*value as u64, true)
-                    .as_basic_value_enum(),
-            )),
+            ASTInfo::ConditionalExpr(cond, then_expr, else_expr) => {
+                let type_ = self.semantics.type_of(ast);
+                self.gen_conditional_expr(type_, cond, then_expr, else_expr)
+            }
+            ASTInfo::CallExpr(name, arguments) => self.gen_function_call(name, arguments),
+            ASTInfo::Variable(_, _) | ASTInfo::MemberExpr(_, _, _) => {
+                let (type_, ptr) = self.gen_lvalue(ast);
+                self.gen_load(&type_, ptr)
+            }
+            ASTInfo::IntLiteral(value) => self
+                .context
+                .i32_type()
+                .const_int(*value as u64, true)
+                .as_basic_value_enum(),
+            // 与C语言一样, 字符字面量是int类型
+            ASTInfo::CharLiteral(value) => self
+                .context
+                .i32_type()
+                .const_int(*value as u64, false)
+                .as_basic_value_enum(),
+            ASTInfo::StringLiteral(value) => self.gen_string_literal(value).as_basic_value_enum(),
             _ => unreachable!(),
         }
     }
 
-    fn gen_binary_expr(
-        &self,
-        op: &Operand,
-        left: &Ast,
-        right: &Ast,
-    ) -> Result<(Type, BasicValueEnum)> {
-        let (lhs, rhs) = (self.gen_expression(left)?.1, self.gen_expression(right)?.1);
-        let lhs = match lhs {
-            BasicValueEnum::IntValue(i) => i,
-            BasicValueEnum::PointerValue(p) => {
-                self.builder
-                    .build_ptr_to_int(p, self.context.i32_type(), "")
+    /// A string literal is a private constant array ending with `'\0'`,
+    /// and its value is the pointer to the first character.
+    fn gen_string_literal(&self, value: &[u8]) -> PointerValue {
+        let i8_type = self.context.i8_type();
+        let mut chars: Vec<IntValue> = value
+            .iter()
+            .map(|c| i8_type.const_int(*c as u64, false))
+            .collect();
+        chars.push(i8_type.const_zero());
+
+        let string = self
+            .module
+            .add_global(i8_type.array_type(chars.len() as u32), None, ".str");
+        string.set_initializer(&i8_type.const_array(&chars));
+        string.set_constant(true);
+        string.set_linkage(Linkage::Private);
+        string.set_unnamed_addr(true);
+
+        let zero = self.context.i32_type().const_int(0, false);
+        unsafe {
+            self.builder
+                .build_in_bounds_gep(string.as_pointer_value(), &[zero, zero], "")
+        }
+    }
+
+    fn gen_unary_expr(&self, op: &UnaryOp, expr: &Ast) -> BasicValueEnum {
+        match op {
+            UnaryOp::Addr => {
+                let (_, ptr) = self.gen_lvalue(expr);
+                return ptr.as_basic_value_enum();
             }
-            _ => unreachable!(),
-        };
-        let rhs = match rhs {
-            BasicValueEnum::IntValue(i) => i,
-            BasicValueEnum::PointerValue(p) => {
-                self.builder
-                    .build_ptr_to_int(p, self.context.i32_type(), "")
+            UnaryOp::Deref => {
+                let (type_, ptr) = self.gen_deref(expr);
+                return self.gen_load(&type_, ptr);
             }
+            UnaryOp::PreInc | UnaryOp::PreDec | UnaryOp::PostInc | UnaryOp::PostDec => {
+                return self.gen_increment(op, expr);
+            }
+            UnaryOp::Lnot => {
+                // !x就是x不满足条件, 所以指针也可以取反
+                let value = self.gen_condition(expr);
+                let value = self.builder.build_not(value, "");
+                // 与gen_binary_expr一样, 比较的结果转换成i32类型
+                let value = self
+                    .builder
+                    .build_int_z_extend(value, self.context.i32_type(), "");
+                return value.as_basic_value_enum();
+            }
+            _ => {}
+        }
+
+        let value = self.gen_expression(expr);
+        let value = self.gen_promotion(self.semantics.type_of(expr), value);
+
+        let value = match op {
+            UnaryOp::Neg => self.builder.build_int_neg(value, ""),
+            UnaryOp::Pos => value,
+            UnaryOp::Bnot => self.builder.build_not(value, ""),
             _ => unreachable!(),
         };
 
+        value.as_basic_value_enum()
+    }
+
+    fn gen_binary_expr(&self, op: &Operand, left: &Ast, right: &Ast) -> BasicValueEnum {
+        if let Operand::Land | Operand::Lor = op {
+            return self.gen_logic_expr(op, left, right);
+        }
+        let lhs = self.gen_expression(left);
+        let rhs = self.gen_expression(right);
+        let lhs_type = self.semantics.type_of(left);
+        let rhs_type = self.semantics.type_of(right);
+        self.gen_binary_value(op, (lhs_type, lhs), (rhs_type, rhs))
+    }
+
+    /// Apply the operation to two values that have been generated,
+    /// which is shared by binary expressions and compound assignments.
+    /// The result of pointer arithmetic is a pointer, and others are `int`.
+    fn gen_binary_value<'a>(
+        &'a self,
+        op: &Operand,
+        (lhs_type, lhs): (&Type, BasicValueEnum<'a>),
+        (rhs_type, rhs): (&Type, BasicValueEnum<'a>),
+    ) -> BasicValueEnum<'a> {
+        if matches!(lhs_type, Type::Ptr(_)) || matches!(rhs_type, Type::Ptr(_)) {
+            return self.gen_pointer_expr(op, (lhs_type, lhs), (rhs_type, rhs));
+        }
+        let lhs = self.gen_promotion(lhs_type, lhs);
+        let rhs = self.gen_promotion(rhs_type, rhs);
+
         let value = match op {
             Operand::Add => self.builder.build_int_add(lhs, rhs, ""),
             Operand::Sub => self.builder.build_int_sub(lhs, rhs, ""),
@@ -473,129 +855,439 @@ impl<'ctx> CodeBuilder<'ctx> {
             Operand::Band => self.builder.build_and(lhs, rhs, ""),
             Operand::Bor => self.builder.build_or(lhs, rhs, ""),
             Operand::Bxor => self.builder.build_xor(lhs, rhs, ""),
-            Operand::Land => {
-                let a = self.builder.build_and(lhs, rhs, "");
-                let b = self.context.i32_type().const_int(0, false);
-                self.builder.build_int_compare(IntPredicate::NE, a, b, "")
-            }
-            Operand::Lor => {
-                let a = self.builder.build_or(lhs, rhs, "");
-                let b = self.context.i32_type().const_int(0, false);
-                self.builder.build_int_compare(IntPredicate::NE, a, b, "")
-            }
+            Operand::Land | Operand::Lor => unreachable!(),
             Operand::LShift => self.builder.build_left_shift(lhs, rhs, ""),
             Operand::RShift => self.builder.build_right_shift(lhs, rhs, true, ""),
         };
         // 其实，更C语言的做法是在运算符两边类型不同的时候进行隐式转换
         // 不过，我这里将所有的类型都转换成了i32类型
-        let value = if value.get_type().get_bit_width() != 32 {
+        if value.get_type().get_bit_width() != 32 {
             self.builder
                 .build_int_z_extend_or_bit_cast(value, self.context.i32_type(), "")
                 .as_basic_value_enum()
         } else {
             value.as_basic_value_enum()
+        }
+    }
+
+    /// Pointer arithmetic and comparison. The integer added to a pointer is scaled by
+    /// the size of the element, and the difference of two pointers is the number of
+    /// elements between them.
+    fn gen_pointer_expr<'a>(
+        &'a self,
+        op: &Operand,
+        (lhs_type, lhs): (&Type, BasicValueEnum<'a>),
+        (rhs_type, rhs): (&Type, BasicValueEnum<'a>),
+    ) -> BasicValueEnum<'a> {
+        match (op, lhs_type, rhs_type) {
+            (Operand::Add | Operand::Sub, Type::Ptr(_), index_type) if index_type.is_integer() => {
+                let mut index = self.gen_promotion(index_type, rhs);
+                if let Operand::Sub = op {
+                    index = self.builder.build_int_neg(index, "");
+                }
+                let ptr = unsafe {
+                    self.builder
+                        .build_in_bounds_gep(lhs.into_pointer_value(), &[index], "")
+                };
+                ptr.as_basic_value_enum()
+            }
+            (Operand::Add, index_type, Type::Ptr(_)) => {
+                let index = self.gen_promotion(index_type, lhs);
+                let ptr = unsafe {
+                    self.builder
+                        .build_in_bounds_gep(rhs.into_pointer_value(), &[index], "")
+                };
+                ptr.as_basic_value_enum()
+            }
+            (Operand::Sub, _, _) => {
+                let value = self.builder.build_ptr_diff(
+                    lhs.into_pointer_value(),
+                    rhs.into_pointer_value(),
+                    "",
+                );
+                let value = self
+                    .builder
+                    .build_int_truncate(value, self.context.i32_type(), "");
+                value.as_basic_value_enum()
+            }
+            _ => {
+                let predicate = match op {
+                    Operand::Eq => IntPredicate::EQ,
+                    Operand::Ne => IntPredicate::NE,
+                    Operand::Lt => IntPredicate::ULT,
+                    Operand::Le => IntPredicate::ULE,
+                    Operand::Gt => IntPredicate::UGT,
+                    Operand::Ge => IntPredicate::UGE,
+                    _ => unreachable!(),
+                };
+                let i64_type = self.context.i64_type();
+                // 与指针比较的整数是空指针常量0
+                let to_int = |value: BasicValueEnum<'a>| match value {
+                    BasicValueEnum::PointerValue(ptr) => {
+                        self.builder.build_ptr_to_int(ptr, i64_type, "")
+                    }
+                    value => self
+                        .builder
+                        .build_int_z_extend(value.into_int_value(), i64_type, ""),
+                };
+                let lhs = to_int(lhs);
+                let rhs = to_int(rhs);
+                let value = self.builder.build_int_compare(predicate, lhs, rhs, "");
+                let value = self
+                    .builder
+                    .build_int_z_extend(value, self.context.i32_type(), "");
+                value.as_basic_value_enum()
+            }
+        }
+    }
+
+    /// `&&` and `||` are short-circuit: the right operand is evaluated only if
+    /// the left operand can't decide the result.
+    fn gen_logic_expr(&self, op: &Operand, left: &Ast, right: &Ast) -> BasicValueEnum {
+        let lhs = self.gen_condition(left);
+        let lhs_block = self.builder.get_insert_block().unwrap();
+        let rhs_block = self
+            .context
+            .insert_basic_block_after(lhs_block, "logic_rhs");
+        let destination_block = self
+            .context
+            .insert_basic_block_after(rhs_block, "logic_dest_block");
+        match op {
+            Operand::Land => {
+                self.builder
+                    .build_conditional_branch(lhs, rhs_block, destination_block)
+            }
+            Operand::Lor => {
+                self.builder
+                    .build_conditional_branch(lhs, destination_block, rhs_block)
+            }
+            _ => unreachable!(),
         };
 
-        Ok((Type::Int, value))
+        self.builder.position_at_end(rhs_block);
+        let rhs = self.gen_condition(right);
+        // 右操作数中可能还有短路运算, 所以结束时的基本块不一定是rhs_block
+        let rhs_block = self.builder.get_insert_block().unwrap();
+        self.builder.build_unconditional_branch(destination_block);
+
+        // 短路时结果就是左操作数的值: &&时为false, ||时为true
+        self.builder.position_at_end(destination_block);
+        let phi = self.builder.build_phi(self.context.bool_type(), "");
+        phi.add_incoming(&[(&lhs, lhs_block), (&rhs, rhs_block)]);
+        let value = self.builder.build_int_z_extend(
+            phi.as_basic_value().into_int_value(),
+            self.context.i32_type(),
+            "",
+        );
+
+        value.as_basic_value_enum()
     }
 
-    fn gen_function_call(
+    /// Only the selected expression is evaluated, and it's converted to
+    /// the type of the conditional expression, e.g. `char` is promoted to `int`.
+    fn gen_conditional_expr(
         &self,
-        position: (usize, usize),
-        name: &str,
-        arguments: &Vec<Ast>,
-    ) -> Result<(Type, BasicValueEnum)> {
-        let mut args = Vec::new();
-        for argument in arguments {
-            let arg = self.gen_expression(argument)?.1;
-            args.push(arg.into())
-        }
-        let function = self.global_functions.get(name);
-        match function {
-            Some((type_, function)) => {
-                let return_value = self.builder.build_call(*function, &args[..], name);
-                match return_value.try_as_basic_value() {
-                    Either::Left(value) => {
-                        if value.get_type() == type_.to_llvm_basic_type(self.context) {
-                            Ok((*type_, value))
-                        } else {
-                            Err(Error::new(position, ErrorType::MismatchedType))?
-                        }
-                    }
-                    Either::Right(_) => Ok((
-                        Type::Void,
-                        self.context
-                            .i32_type()
-                            .const_int(0, false)
-                            .as_basic_value_enum(),
-                    )),
-                }
-            }
-            None => Err(Error::new(position, ErrorType::FunctionNotDefined))?,
+        type_: &Type,
+        cond: &Ast,
+        then_expr: &Ast,
+        else_expr: &Ast,
+    ) -> BasicValueEnum {
+        let comparison = self.gen_condition(cond);
+        let current_block = self.builder.get_insert_block().unwrap();
+        let then_block = self
+            .context
+            .insert_basic_block_after(current_block, "cond_then");
+        let else_block = self
+            .context
+            .insert_basic_block_after(then_block, "cond_else");
+        let destination_block = self
+            .context
+            .insert_basic_block_after(else_block, "cond_dest_block");
+        self.builder
+            .build_conditional_branch(comparison, then_block, else_block);
+
+        let mut arms = vec![];
+        for (block, expr) in [(then_block, then_expr), (else_block, else_expr)] {
+            self.builder.position_at_end(block);
+            let value = self.gen_expression(expr);
+            let value = self.gen_implicit_cast(value, type_);
+            // 表达式中可能还有分支, 所以结束时的基本块不一定是开始时的基本块
+            arms.push((value, self.builder.get_insert_block().unwrap()));
+            self.builder.build_unconditional_branch(destination_block);
         }
+        let (else_value, else_block) = arms.pop().unwrap();
+        let (then_value, then_block) = arms.pop().unwrap();
+
+        self.builder.position_at_end(destination_block);
+        let phi = self.builder.build_phi(then_value.get_type(), "");
+        phi.add_incoming(&[(&then_value, then_block), (&else_value, else_block)]);
+        phi.as_basic_value()
     }
 
-    fn gen_assignment_expr(&self, var: &Ast, expr: &Ast) -> Result<(Type, BasicValueEnum)> {
-        let var_info = &var.info;
-        if let ASTInfo::Variable(name, index) = var_info {
-            let (type_left, ptr) =
-                self.gen_variable(var.position, name, &index.as_ref().map(|x| x.as_ref()))?;
-            let (type_right, value) = self.gen_expression(expr)?;
-            if type_left == type_right {
-                self.builder.build_store(ptr, value);
-                Ok((type_left, value.as_basic_value_enum()))
-            } else {
-                Err(Error::new(var.position, ErrorType::MismatchedType))?
+    /// The value of a call to a void function is a placeholder that is never used.
+    fn gen_function_call(&self, name: &str, arguments: &[Ast]) -> BasicValueEnum {
+        let (_, param_types, function) = &self.global_functions[name];
+        debug_assert_eq!(arguments.len(), param_types.len());
+        let args: Vec<BasicMetadataValueEnum> = arguments
+            .iter()
+            .zip(param_types)
+            .map(|(argument, param_type)| {
+                let arg = self.gen_expression(argument);
+                // 与赋值一样进行转换, 例如char实参传给int形参
+                self.gen_implicit_cast(arg, param_type).into()
+            })
+            .collect();
+        let return_value = self.builder.build_call(*function, &args[..], name);
+        match return_value.try_as_basic_value() {
+            Either::Left(value) => value,
+            Either::Right(_) => self
+                .context
+                .i32_type()
+                .const_int(0, false)
+                .as_basic_value_enum(),
+        }
+    }
+
+    /// The address of the lvalue is generated only once, so `a[f(i)] += 1` calls `f` once.
+    fn gen_assignment_expr(&self, op: Option<&Operand>, var: &Ast, expr: &Ast) -> BasicValueEnum {
+        let (type_left, ptr) = self.gen_lvalue(var);
+        let right = self.gen_expression(expr);
+        let right_type = self.semantics.type_of(expr);
+        let value = match op {
+            Some(op) => {
+                let left = self.gen_load(&type_left, ptr);
+                self.gen_binary_value(op, (&type_left, left), (right_type, right))
             }
-        } else {
-            unreachable!()
+            None => right,
+        };
+        self.gen_store(&type_left, ptr, value)
+    }
+
+    /// `++x` and `--x` are `x += 1` and `x -= 1`, `x++` and `x--` return the old value.
+    fn gen_increment(&self, op: &UnaryOp, expr: &Ast) -> BasicValueEnum {
+        let (type_, ptr) = self.gen_lvalue(expr);
+        let old = self.gen_load(&type_, ptr);
+        let one = self
+            .context
+            .i32_type()
+            .const_int(1, false)
+            .as_basic_value_enum();
+        let operand = match op {
+            UnaryOp::PreInc | UnaryOp::PostInc => Operand::Add,
+            _ => Operand::Sub,
+        };
+        let new = self.gen_binary_value(&operand, (&type_, old), (&Type::Int, one));
+        let new = self.gen_store(&type_, ptr, new);
+        match op {
+            UnaryOp::PreInc | UnaryOp::PreDec => new,
+            _ => old,
         }
     }
 
-    fn gen_variable(
-        &self,
-        position: (usize, usize),
-        name: &str,
-        index: &Option<&Ast>,
-    ) -> Result<(Type, PointerValue)> {
-        let (type_, ptr) = self.get_name_ptr(position, name)?;
+    /// Convert the value to the type of the lvalue and store it,
+    /// the converted value is the value of the assignment.
+    fn gen_store<'a>(
+        &'a self,
+        type_: &Type,
+        ptr: PointerValue<'a>,
+        value: BasicValueEnum<'a>,
+    ) -> BasicValueEnum<'a> {
+        let value = self.gen_implicit_cast(value, type_);
+        self.builder.build_store(ptr, value);
+        value
+    }
+
+    /// Generate the type and address of a lvalue, which is a variable,
+    /// a member of struct or `*p`, e.g. `m[i][j]`, `s.a[i]` and `*(p + 1)`.
+    fn gen_lvalue(&self, ast: &Ast) -> (Type, PointerValue) {
+        match &ast.info {
+            ASTInfo::Variable(name, indices) => self.gen_variable(name, indices),
+            ASTInfo::MemberExpr(base, member, indices) => self.gen_member(base, member, indices),
+            ASTInfo::UnaryExpr(UnaryOp::Deref, expr) => self.gen_deref(expr),
+            _ => unreachable!(),
+        }
+    }
+
+    /// The address that the pointer expression of `*p` points to.
+    fn gen_deref(&self, expr: &Ast) -> (Type, PointerValue) {
+        let value = self.gen_expression(expr);
+        match self.semantics.type_of(expr) {
+            Type::Ptr(type_) => (*type_.clone(), value.into_pointer_value()),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Load the value of a lvalue. An array is converted to the pointer to
+    /// its first element instead, e.g. when it's passed to a `int a[]` parameter.
+    fn gen_load<'a>(&'a self, type_: &Type, ptr: PointerValue<'a>) -> BasicValueEnum<'a> {
         match type_ {
-            Type::Int => Ok((type_, ptr)),
-            Type::Void => Err(Error::new(position, ErrorType::ExpressionVoidType))?,
-            Type::IntPtr => {
-                if let Some(index) = index {
-                    let (index_type, index) = self.gen_expression(index)?;
-                    if index_type == Type::Int {
-                        let ptr = self.builder.build_load(ptr, "").into_pointer_value();
-                        unsafe {
-                            let ptr = self.builder.build_in_bounds_gep(
-                                ptr,
-                                &[index.into_int_value()],
-                                "",
-                            );
-                            Ok((Type::Int, ptr))
-                        }
-                    } else {
-                        Err(Error::new(position, ErrorType::IndexNotInt))?
-                    }
-                } else {
-                    Ok((type_, ptr))
+            Type::Array(_, _) => {
+                let zero = self.context.i32_type().const_int(0, false);
+                let ptr = unsafe { self.builder.build_in_bounds_gep(ptr, &[zero, zero], "") };
+                ptr.as_basic_value_enum()
+            }
+            _ => self.builder.build_load(ptr, ""),
+        }
+    }
+
+    /// Generate the address of a variable, e.g. `m[i][j]` of `int m[10][20]`.
+    fn gen_variable(&self, name: &str, indices: &[Ast]) -> (Type, PointerValue) {
+        let (type_, ptr) = self.get_name_ptr(name);
+        self.gen_indices(type_, ptr, indices)
+    }
+
+    /// Generate the address of a member of struct, e.g. `s.a[i]`.
+    fn gen_member(&self, base: &Ast, member: &str, indices: &[Ast]) -> (Type, PointerValue) {
+        let (type_, ptr) = match self.gen_lvalue(base) {
+            (Type::Struct(name), ptr) => {
+                let fields = &self.struct_types[&name].1;
+                let index = fields
+                    .iter()
+                    .position(|(_, field)| field == member)
+                    .unwrap();
+                let ptr = self
+                    .builder
+                    .build_struct_gep(ptr, index as u32, member)
+                    .unwrap();
+                (fields[index].0.clone(), ptr)
+            }
+            _ => unreachable!(),
+        };
+        self.gen_indices(type_, ptr, indices)
+    }
+
+    /// Every index selects an element of the array or the pointer.
+    fn gen_indices<'a>(
+        &'a self,
+        mut type_: Type,
+        mut ptr: PointerValue<'a>,
+        indices: &[Ast],
+    ) -> (Type, PointerValue<'a>) {
+        for index in indices {
+            let index_value = self.gen_expression(index);
+            let index_value = self.gen_promotion(self.semantics.type_of(index), index_value);
+            (type_, ptr) = match type_ {
+                // 指针变量中保存的是第一个元素的地址, 需要先读出指针
+                Type::Ptr(elem_type) => {
+                    let ptr = self.builder.build_load(ptr, "").into_pointer_value();
+                    let ptr = unsafe { self.builder.build_in_bounds_gep(ptr, &[index_value], "") };
+                    (*elem_type, ptr)
+                }
+                Type::Array(elem_type, _) => {
+                    let zero = self.context.i32_type().const_int(0, false);
+                    let ptr = unsafe {
+                        self.builder
+                            .build_in_bounds_gep(ptr, &[zero, index_value], "")
+                    };
+                    (*elem_type, ptr)
                 }
+                _ => unreachable!(),
+            };
+        }
+        (type_, ptr)
+    }
+
+    fn get_name_ptr(&self, name: &str) -> (Type, PointerValue) {
+        self.variables_stack
+            .iter()
+            .rev()
+            .find_map(|domain| domain.get(name))
+            .or_else(|| self.global_variables.get(name))
+            .cloned()
+            .unwrap()
+    }
+
+    /// Generate the value of a condition, which is true if the expression is not zero.
+    fn gen_condition(&self, ast: &Ast) -> IntValue {
+        let value = match self.gen_expression(ast) {
+            BasicValueEnum::IntValue(i) => i,
+            BasicValueEnum::PointerValue(p) => {
+                return self.builder.build_is_not_null(p, "condition");
             }
             _ => unreachable!(),
+        };
+        let zero = value.get_type().const_int(0, false);
+        self.builder
+            .build_int_compare(IntPredicate::NE, value, zero, "condition")
+    }
+
+    /// Integer promotion: `char` is sign extended to `int` before arithmetic.
+    fn gen_promotion<'a>(&'a self, type_: &Type, value: BasicValueEnum<'a>) -> IntValue<'a> {
+        match value {
+            BasicValueEnum::IntValue(i) if *type_ == Type::Char => {
+                self.builder
+                    .build_int_s_extend(i, self.context.i32_type(), "")
+            }
+            BasicValueEnum::IntValue(i) => i,
+            _ => unreachable!(),
         }
     }
 
-    fn get_name_ptr(&self, position: (usize, usize), name: &str) -> Result<(Type, PointerValue)> {
-        for domain in self.variables_stack.iter().rev() {
-            if let Some(ptr) = domain.get(name) {
-                return Ok(*ptr);
+    /// Convert the value for assignment and return.
+    /// Integer types are converted to each other, other types are already the same.
+    /// An integer assigned to a pointer is the null pointer constant `0`.
+    fn gen_implicit_cast<'a>(&'a self, value: BasicValueEnum<'a>, to: &Type) -> BasicValueEnum<'a> {
+        match value {
+            BasicValueEnum::IntValue(_) if matches!(to, Type::Ptr(_)) => {
+                self.to_llvm_const_zero(to)
+            }
+            BasicValueEnum::IntValue(i) if to.is_integer() => {
+                let int_type = self.to_llvm_basic_type(to).into_int_type();
+                self.builder
+                    .build_int_cast(i, int_type, "")
+                    .as_basic_value_enum()
             }
+            _ => value,
+        }
+    }
+
+    fn to_llvm_basic_type(&self, type_: &Type) -> BasicTypeEnum<'ctx> {
+        match type_ {
+            Type::Int => self.context.i32_type().as_basic_type_enum(),
+            Type::Char => self.context.i8_type().as_basic_type_enum(),
+            Type::Void => panic!("Variable have void type"),
+            Type::Array(type_, size) => self
+                .to_llvm_basic_type(type_)
+                .array_type(*size as u32)
+                .as_basic_type_enum(),
+            // void*与char*一样使用i8*
+            Type::Ptr(box Type::Void) => self
+                .context
+                .i8_type()
+                .ptr_type(AddressSpace::Generic)
+                .as_basic_type_enum(),
+            Type::Ptr(type_) => self
+                .to_llvm_basic_type(type_)
+                .ptr_type(AddressSpace::Generic)
+                .as_basic_type_enum(),
+            Type::Struct(name) => self.struct_types[name].0.as_basic_type_enum(),
+        }
+    }
+
+    fn to_llvm_basic_metadata_type(&self, type_: &Type) -> BasicMetadataTypeEnum<'ctx> {
+        self.to_llvm_basic_type(type_).into()
+    }
+
+    /// The zero value of the type, used to initialize variables.
+    fn to_llvm_const_zero(&self, type_: &Type) -> BasicValueEnum<'ctx> {
+        match self.to_llvm_basic_type(type_) {
+            BasicTypeEnum::IntType(t) => t.const_zero().as_basic_value_enum(),
+            BasicTypeEnum::ArrayType(t) => t.const_zero().as_basic_value_enum(),
+            BasicTypeEnum::PointerType(t) => t.const_null().as_basic_value_enum(),
+            BasicTypeEnum::StructType(t) => t.const_zero().as_basic_value_enum(),
+            _ => unreachable!(),
         }
-        if let Some(ptr) = self.global_variables.get(name) {
-            return Ok(*ptr);
+    }
+
+    /// Continue generating code after a statement in its destination block.
+    /// If nothing jumps to the block, the code after the statement is unreachable,
+    /// so the block is removed and the builder stays in the terminated block.
+    fn position_at_destination(&self, destination_block: BasicBlock) {
+        if destination_block.get_first_use().is_some() {
+            self.builder.position_at_end(destination_block);
+        } else {
+            destination_block.remove_from_function().unwrap();
         }
-        Err(Error::new(position, ErrorType::VariableNotDefined))?
     }
 
     fn no_terminator(&self) -> bool {
@@ -609,36 +1301,20 @@ impl<'ctx> CodeBuilder<'ctx> {
 
 #[cfg(test)]
 mod test_parse {
-    use std::{
-        fs::{self, File},
-        io::Read,
-        os::unix::prelude::OsStringExt,
-        path::Path,
-    };
+    use std::path::Path;
 
     use inkwell::context::Context;
 
     use super::CodeBuilder;
+    use crate::{sema::analyze, test_util::for_each_source};
 
     fn codegen_ok_test(ok_path: &Path) {
-        for source in fs::read_dir(ok_path).unwrap() {
-            let source = source.unwrap();
-            if source.file_type().unwrap().is_file()
-                && source.file_name().into_vec().ends_with(b".c")
-            {
-                let mut file = File::open(source.path()).unwrap();
-
-                println!("Test source code file {:?}", source);
-                let mut buf = String::new();
-                file.read_to_string(&mut buf).unwrap();
-
-                let ast = super::Ast::parse(buf).unwrap();
-                let context = Context::create();
-                let codegen = CodeBuilder::new(&context, "test", &ast, false)
-                    .expect("Source code file test failed");
-                codegen.build_llvmir(Path::new("test.ll"));
-            }
-        }
+        for_each_source(ok_path, |ast| {
+            let semantics = analyze(&ast).unwrap();
+            let context = Context::create();
+            let codegen = CodeBuilder::new(&context, "test", &ast, semantics, false);
+            codegen.build_llvmir(Path::new("test.ll"));
+        });
     }
 
     #[test]
@@ -1,4 +1,4 @@
-use std::{error, fmt::Display};
+use std::{error, fmt::Display, str::FromStr};
 
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -12,6 +12,10 @@ impl Error {
     pub fn new(position: (usize, usize), error: ErrorType) -> Self {
         Self { position, error }
     }
+
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
 }
 impl From<pest::error::Error<crate::parser::Rule>> for Error {
     fn from(e: pest::error::Error<crate::parser::Rule>) -> Self {
@@ -43,6 +47,33 @@ impl Display for Error {
                 ErrorType::MismatchedTypeFunction => "Mismatched type of Function's return type",
                 ErrorType::FunctionNotDefined => "Function has not been defined",
                 ErrorType::ExpressionVoidType => "Expression has void type",
+                ErrorType::BreakOutsideLoop => "Break statement not within a loop",
+                ErrorType::ContinueOutsideLoop => "Continue statement not within a loop",
+                ErrorType::IntegerLiteralOverflow => "Integer literal is too large for int type",
+                ErrorType::InitializerNotConstant => {
+                    "Initializer of global variable is not a constant expression"
+                }
+                ErrorType::ArraySizeMissing => "Array size is missing",
+                ErrorType::TooManyInitializers => "Too many initializers",
+                ErrorType::NotSubscriptable => "Subscripted value is neither array nor pointer",
+                ErrorType::NotLvalue => "Expression is not assignable",
+                ErrorType::UnknownMember => "Struct has no member with this name",
+                ErrorType::StructNotDefined => "Struct not defined",
+                ErrorType::StructRedefinition => "Struct redefinition",
+                ErrorType::StructContainsItself => "Struct contains itself",
+                ErrorType::CaseLabelNotConstant => "Case label is not a constant expression",
+                ErrorType::DuplicateCaseLabel => "Duplicate case label",
+                ErrorType::MismatchedPrototype => "Function does not match its prototype",
+                ErrorType::VoidVariable => "Variable has void type",
+                ErrorType::ArgumentCountMismatch => {
+                    "Number of arguments does not match the function's parameters"
+                }
+                ErrorType::ArgumentTypeMismatch => {
+                    "Type of argument does not match the function's parameter"
+                }
+                ErrorType::MissingReturn => {
+                    "Non-void function does not return a value in all control paths"
+                }
                 ErrorType::PestError(ref s) => s.as_str(),
             }
         )
@@ -58,5 +89,119 @@ pub enum ErrorType {
     MismatchedTypeFunction,
     FunctionNotDefined,
     ExpressionVoidType,
+    BreakOutsideLoop,
+    ContinueOutsideLoop,
+    IntegerLiteralOverflow,
+    InitializerNotConstant,
+    ArraySizeMissing,
+    TooManyInitializers,
+    NotSubscriptable,
+    NotLvalue,
+    UnknownMember,
+    StructNotDefined,
+    StructRedefinition,
+    StructContainsItself,
+    CaseLabelNotConstant,
+    DuplicateCaseLabel,
+    MismatchedPrototype,
+    VoidVariable,
+    ArgumentCountMismatch,
+    ArgumentTypeMismatch,
+    MissingReturn,
     PestError(String),
 }
+
+/// A warning doesn't stop the compilation unless warnings are turned into errors.
+#[derive(Debug)]
+pub struct Warning {
+    position: (usize, usize),
+    warning: WarningType,
+    /// The variable or function that the warning is about.
+    name: Option<String>,
+}
+
+impl Warning {
+    pub fn new(position: (usize, usize), warning: WarningType) -> Self {
+        Self {
+            position,
+            warning,
+            name: None,
+        }
+    }
+
+    pub fn named(position: (usize, usize), warning: WarningType, name: &str) -> Self {
+        Self {
+            position,
+            warning,
+            name: Some(name.to_string()),
+        }
+    }
+
+    pub fn position(&self) -> (usize, usize) {
+        self.position
+    }
+
+    pub fn warning_type(&self) -> WarningType {
+        self.warning
+    }
+}
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = self.name.as_deref().unwrap_or_default();
+        write!(
+            f,
+            "{}:{}: {} [-W{}]",
+            self.position.0,
+            self.position.1,
+            match self.warning {
+                WarningType::UnusedVariable => format!("Unused variable `{}`", name),
+                WarningType::UnusedParameter => format!("Unused parameter `{}`", name),
+                WarningType::UnusedFunction => format!("Function `{}` is never called", name),
+                WarningType::UnusedButSetVariable =>
+                    format!("Variable `{}` is written but never read", name),
+                WarningType::UnreachableCode => "Code will never be executed".to_string(),
+                WarningType::Uninitialized =>
+                    format!("Variable `{}` may be used before it is initialized", name),
+            },
+            self.warning.name()
+        )
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarningType {
+    UnusedVariable,
+    UnusedParameter,
+    UnusedFunction,
+    UnusedButSetVariable,
+    UnreachableCode,
+    Uninitialized,
+}
+
+impl WarningType {
+    /// The name used by command line options, e.g. `-Wno-unused-variable`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarningType::UnusedVariable => "unused-variable",
+            WarningType::UnusedParameter => "unused-parameter",
+            WarningType::UnusedFunction => "unused-function",
+            WarningType::UnusedButSetVariable => "unused-but-set-variable",
+            WarningType::UnreachableCode => "unreachable-code",
+            WarningType::Uninitialized => "uninitialized",
+        }
+    }
+}
+impl FromStr for WarningType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "unused-variable" => Ok(WarningType::UnusedVariable),
+            "unused-parameter" => Ok(WarningType::UnusedParameter),
+            "unused-function" => Ok(WarningType::UnusedFunction),
+            "unused-but-set-variable" => Ok(WarningType::UnusedButSetVariable),
+            "unreachable-code" => Ok(WarningType::UnreachableCode),
+            "uninitialized" => Ok(WarningType::Uninitialized),
+            _ => Err(format!("Unknown warning `{}`", s)),
+        }
+    }
+}
@@ -0,0 +1,416 @@
+use crate::error::{Warning, WarningType};
+use crate::parser::{ASTInfo, Ast, Operand, Param, Type, UnaryOp};
+use std::collections::{HashMap, HashSet};
+
+/// Whether the statement can complete normally, i.e. the statement after it can be reached.
+/// The first unreachable statement of every statement list is reported.
+pub fn can_complete(stmt: &Ast, warnings: &mut Vec<Warning>) -> bool {
+    Reachability {
+        targets: vec![],
+        warnings,
+    }
+    .statement(stmt)
+}
+
+/// The `break` and `continue` that are reached in a loop or switch.
+struct Target {
+    is_loop: bool,
+    has_break: bool,
+    has_continue: bool,
+}
+
+/// Only reachable statements are visited, so an unreachable `break` doesn't end a loop.
+struct Reachability<'a> {
+    /// Loops and switches containing the statement being visited.
+    targets: Vec<Target>,
+    warnings: &'a mut Vec<Warning>,
+}
+
+impl Reachability<'_> {
+    fn statement(&mut self, stmt: &Ast) -> bool {
+        match &stmt.info {
+            ASTInfo::BlockStmt(_, statements) => self.statements(statements),
+            ASTInfo::SelectionStmt(_, then_stmt, else_stmt) => {
+                let then_completes = self.statement(then_stmt);
+                match else_stmt {
+                    Some(else_stmt) => self.statement(else_stmt) || then_completes,
+                    None => true,
+                }
+            }
+            // 条件恒为真的循环只能通过break结束
+            ASTInfo::IterationStmt(cond, loop_stmt) => {
+                let (_, target) = self.loop_body(loop_stmt);
+                !is_always_true(Some(cond.as_ref())) || target.has_break
+            }
+            ASTInfo::DoWhileStmt(loop_stmt, cond) => {
+                let (completes, target) = self.loop_body(loop_stmt);
+                let reach_cond = completes || target.has_continue;
+                (reach_cond && !is_always_true(Some(cond.as_ref()))) || target.has_break
+            }
+            ASTInfo::ForStmt(_, cond, _, loop_stmt) => {
+                let (_, target) = self.loop_body(loop_stmt);
+                !is_always_true(cond.as_deref()) || target.has_break
+            }
+            // 每个case都可以通过标号到达, 只有最后一个case能执行到switch的结尾
+            ASTInfo::SwitchStmt(_, cases) => {
+                self.targets.push(Target {
+                    is_loop: false,
+                    has_break: false,
+                    has_continue: false,
+                });
+                let mut last_completes = true;
+                let mut has_default = false;
+                for case in cases {
+                    if let ASTInfo::CaseStmt(label, statements) = &case.info {
+                        has_default |= label.is_none();
+                        last_completes = self.statements(statements);
+                    }
+                }
+                let target = self.targets.pop().unwrap();
+                last_completes || !has_default || target.has_break
+            }
+            ASTInfo::BreakStmt => {
+                if let Some(target) = self.targets.last_mut() {
+                    target.has_break = true;
+                }
+                false
+            }
+            // switch中的continue属于外层的循环
+            ASTInfo::ContinueStmt => {
+                if let Some(target) = self.targets.iter_mut().rev().find(|t| t.is_loop) {
+                    target.has_continue = true;
+                }
+                false
+            }
+            ASTInfo::ReturnStmt(_) => false,
+            _ => true,
+        }
+    }
+
+    fn statements(&mut self, statements: &[Ast]) -> bool {
+        for (index, stmt) in statements.iter().enumerate() {
+            if !self.statement(stmt) {
+                if let Some(next) = statements.get(index + 1) {
+                    self.warnings
+                        .push(Warning::new(next.position, WarningType::UnreachableCode));
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether the loop body can complete, and the jumps reached in it.
+    fn loop_body(&mut self, loop_stmt: &Ast) -> (bool, Target) {
+        self.targets.push(Target {
+            is_loop: true,
+            has_break: false,
+            has_continue: false,
+        });
+        let completes = self.statement(loop_stmt);
+        (completes, self.targets.pop().unwrap())
+    }
+}
+
+/// The condition of a loop is a non-zero constant or missing.
+fn is_always_true(cond: Option<&Ast>) -> bool {
+    match cond {
+        Some(cond) => matches!(cond.eval_const(), Some(v) if v != 0),
+        None => true,
+    }
+}
+
+/// Scalar local variables that have been assigned on every path to a point,
+/// `None` if the point is unreachable.
+type Assigned = Option<HashSet<usize>>;
+
+/// The meet of two paths: a variable is assigned if it's assigned on both paths.
+fn join(a: Assigned, b: Assigned) -> Assigned {
+    match (a, b) {
+        (None, state) | (state, None) => state,
+        (Some(a), Some(b)) => Some(a.intersection(&b).copied().collect()),
+    }
+}
+
+/// The loop ends when the condition is false or by `break`.
+fn loop_exit(cond: Option<&Ast>, cond_state: Assigned, break_state: Assigned) -> Assigned {
+    if is_always_true(cond) {
+        break_state
+    } else {
+        join(cond_state, break_state)
+    }
+}
+
+/// Definite assignment analysis of a function body. A scalar local variable which may be read
+/// before it's assigned is reported at the read, once for every variable.
+pub fn check_initialization(params: &[Param], body: &Ast, warnings: &mut Vec<Warning>) {
+    let params = params
+        .iter()
+        .map(|(_, name, _)| (name.clone(), None))
+        .collect();
+    let mut checker = InitChecker {
+        scopes: vec![params],
+        names: vec![],
+        jumps: vec![],
+        reported: HashSet::new(),
+        warnings,
+    };
+    checker.statement(body, Some(HashSet::new()));
+}
+
+/// The states at `break` and `continue` of a loop or switch.
+struct Jumps {
+    is_loop: bool,
+    break_state: Assigned,
+    continue_state: Assigned,
+}
+
+struct InitChecker<'a> {
+    /// Map variables' name to their slots, `None` for parameters, arrays and structs,
+    /// which are not checked.
+    scopes: Vec<HashMap<String, Option<usize>>>,
+    /// Names of the checked variables, indexed by their slots.
+    names: Vec<String>,
+    /// Loops and switches that are being checked.
+    jumps: Vec<Jumps>,
+    /// Variables that have been reported.
+    reported: HashSet<usize>,
+    warnings: &'a mut Vec<Warning>,
+}
+
+impl<'a> InitChecker<'a> {
+    /// The state after the statement.
+    fn statement(&mut self, stmt: &Ast, state: Assigned) -> Assigned {
+        let mut assigned = state?;
+        match &stmt.info {
+            ASTInfo::BlockStmt(variables, statements) => {
+                self.scopes.push(HashMap::new());
+                for var in variables {
+                    if let ASTInfo::VariableDec(type_, name, init) = &var.info {
+                        let slot = match type_ {
+                            Type::Int | Type::Char | Type::Ptr(_) => Some(self.names.len()),
+                            _ => None,
+                        };
+                        self.names.push(name.clone());
+                        self.scopes.last_mut().unwrap().insert(name.clone(), slot);
+                        if let Some(init) = init {
+                            self.expression(init, &mut assigned);
+                            assigned.extend(slot);
+                        }
+                    }
+                }
+                let state = statements.iter().try_fold(assigned, |assigned, stmt| {
+                    self.statement(stmt, Some(assigned))
+                });
+                self.scopes.pop();
+                state
+            }
+            ASTInfo::SelectionStmt(cond, then_stmt, else_stmt) => {
+                self.expression(cond, &mut assigned);
+                let then_state = self.statement(then_stmt, Some(assigned.clone()));
+                let else_state = match else_stmt {
+                    Some(else_stmt) => self.statement(else_stmt, Some(assigned)),
+                    None => Some(assigned),
+                };
+                join(then_state, else_state)
+            }
+            // 循环体只会增加已赋值的变量, 所以循环开始时的状态就是进入循环时的状态
+            ASTInfo::IterationStmt(cond, loop_stmt) => {
+                self.expression(cond, &mut assigned);
+                let (_, jumps) = self.loop_body(loop_stmt, Some(assigned.clone()));
+                loop_exit(Some(cond.as_ref()), Some(assigned), jumps.break_state)
+            }
+            ASTInfo::DoWhileStmt(loop_stmt, cond) => {
+                let (end_state, jumps) = self.loop_body(loop_stmt, Some(assigned));
+                let mut cond_state = join(end_state, jumps.continue_state);
+                if let Some(assigned) = &mut cond_state {
+                    self.expression(cond, assigned);
+                }
+                loop_exit(Some(cond.as_ref()), cond_state, jumps.break_state)
+            }
+            ASTInfo::ForStmt(init, cond, step, loop_stmt) => {
+                let mut state = Some(assigned);
+                if let Some(init) = init {
+                    state = self.statement(init, state);
+                }
+                if let Some(cond) = cond && let Some(assigned) = &mut state {
+                    self.expression(cond, assigned);
+                }
+                let (end_state, jumps) = self.loop_body(loop_stmt, state.clone());
+                if let Some(step) = step {
+                    self.statement(step, join(end_state, jumps.continue_state));
+                }
+                loop_exit(cond.as_deref(), state, jumps.break_state)
+            }
+            ASTInfo::SwitchStmt(cond, cases) => {
+                self.expression(cond, &mut assigned);
+                self.jumps.push(Jumps {
+                    is_loop: false,
+                    break_state: None,
+                    continue_state: None,
+                });
+                // 每个case从switch跳转或者从上一个case贯穿进入
+                let mut state = None;
+                let mut has_default = false;
+                for case in cases {
+                    if let ASTInfo::CaseStmt(label, statements) = &case.info {
+                        has_default |= label.is_none();
+                        state = statements
+                            .iter()
+                            .fold(join(state, Some(assigned.clone())), |state, stmt| {
+                                self.statement(stmt, state)
+                            });
+                    }
+                }
+                let jumps = self.jumps.pop().unwrap();
+                let state = join(state, jumps.break_state);
+                if has_default {
+                    state
+                } else {
+                    join(state, Some(assigned))
+                }
+            }
+            ASTInfo::ReturnStmt(ret_value) => {
+                if let Some(ret_value) = ret_value {
+                    self.expression(ret_value, &mut assigned);
+                }
+                None
+            }
+            // 循环之外的break和continue已经在语义分析中报错
+            ASTInfo::BreakStmt => {
+                if let Some(jumps) = self.jumps.last_mut() {
+                    jumps.break_state = join(jumps.break_state.take(), Some(assigned));
+                }
+                None
+            }
+            ASTInfo::ContinueStmt => {
+                if let Some(jumps) = self.jumps.iter_mut().rev().find(|j| j.is_loop) {
+                    jumps.continue_state = join(jumps.continue_state.take(), Some(assigned));
+                }
+                None
+            }
+            _ => {
+                self.expression(stmt, &mut assigned);
+                Some(assigned)
+            }
+        }
+    }
+
+    /// The state at the end of the loop body, and the states at `break` and `continue`.
+    fn loop_body(&mut self, loop_stmt: &Ast, state: Assigned) -> (Assigned, Jumps) {
+        self.jumps.push(Jumps {
+            is_loop: true,
+            break_state: None,
+            continue_state: None,
+        });
+        let end_state = self.statement(loop_stmt, state);
+        (end_state, self.jumps.pop().unwrap())
+    }
+
+    fn expression(&mut self, ast: &Ast, assigned: &mut HashSet<usize>) {
+        match &ast.info {
+            ASTInfo::AssignmentExpr(op, var, expr) => {
+                self.expression(expr, assigned);
+                if let Some(slot) = self.lvalue(var, assigned) {
+                    // 复合赋值先读取变量的值
+                    if op.is_some() {
+                        self.read(slot, var.position, assigned);
+                    }
+                    assigned.insert(slot);
+                }
+            }
+            ASTInfo::UnaryExpr(UnaryOp::Addr, expr) => {
+                // 取地址之后可能通过指针赋值, 视为已经赋值
+                if let Some(slot) = self.lvalue(expr, assigned) {
+                    assigned.insert(slot);
+                }
+            }
+            ASTInfo::UnaryExpr(
+                UnaryOp::PreInc | UnaryOp::PreDec | UnaryOp::PostInc | UnaryOp::PostDec,
+                expr,
+            ) => {
+                if let Some(slot) = self.lvalue(expr, assigned) {
+                    self.read(slot, expr.position, assigned);
+                    assigned.insert(slot);
+                }
+            }
+            ASTInfo::UnaryExpr(_, expr) => self.expression(expr, assigned),
+            // 短路求值时右边的赋值不一定执行
+            ASTInfo::BinaryExpr(Operand::Land | Operand::Lor, lhs, rhs) => {
+                self.expression(lhs, assigned);
+                self.expression(rhs, &mut assigned.clone());
+            }
+            ASTInfo::BinaryExpr(_, lhs, rhs) => {
+                self.expression(lhs, assigned);
+                self.expression(rhs, assigned);
+            }
+            ASTInfo::ConditionalExpr(cond, then_expr, else_expr) => {
+                self.expression(cond, assigned);
+                let mut then_assigned = assigned.clone();
+                self.expression(then_expr, &mut then_assigned);
+                self.expression(else_expr, assigned);
+                assigned.retain(|slot| then_assigned.contains(slot));
+            }
+            ASTInfo::CallExpr(_, arguments) | ASTInfo::InitList(arguments) => {
+                for argument in arguments {
+                    self.expression(argument, assigned);
+                }
+            }
+            ASTInfo::Variable(_, _) | ASTInfo::MemberExpr(_, _, _) => {
+                if let Some(slot) = self.lvalue(ast, assigned) {
+                    self.read(slot, ast.position, assigned);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Check the expressions in the lvalue, returns the slot if it's a checked variable itself.
+    fn lvalue(&mut self, ast: &Ast, assigned: &mut HashSet<usize>) -> Option<usize> {
+        match &ast.info {
+            ASTInfo::Variable(name, indices) => {
+                for index in indices {
+                    self.expression(index, assigned);
+                }
+                let slot = self.lookup(name)?;
+                if indices.is_empty() {
+                    Some(slot)
+                } else {
+                    // 下标运算读取指针的值
+                    self.read(slot, ast.position, assigned);
+                    None
+                }
+            }
+            ASTInfo::MemberExpr(base, _, indices) => {
+                self.lvalue(base, assigned);
+                for index in indices {
+                    self.expression(index, assigned);
+                }
+                None
+            }
+            _ => {
+                self.expression(ast, assigned);
+                None
+            }
+        }
+    }
+
+    fn read(&mut self, slot: usize, position: (usize, usize), assigned: &HashSet<usize>) {
+        if !assigned.contains(&slot) && self.reported.insert(slot) {
+            self.warnings.push(Warning::named(
+                position,
+                WarningType::Uninitialized,
+                &self.names[slot],
+            ));
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .copied()
+            .flatten()
+    }
+}
@@ -8,4 +8,8 @@ int input() {
     int number;
     scanf("%d", &number);
     return number;
-}
\ No newline at end of file
+}
+void print_string(char s[]) {
+    printf("%s", s);
+    return;
+}
@@ -8,9 +8,15 @@ extern crate pest_derive;
 mod ast_viz;
 mod codegen;
 mod error;
+mod flow;
 mod parser;
+mod sema;
+#[cfg(test)]
+mod test_util;
 
 pub use ast_viz::DiGraph;
 pub use codegen::CodeBuilder;
+pub use error::{Warning, WarningType};
 pub use inkwell::context::Context;
 pub use parser::Ast;
+pub use sema::{analyze, Semantics};
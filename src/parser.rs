@@ -1,10 +1,12 @@
-use crate::error::Error;
-use inkwell::{
-    context::Context,
-    types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum},
+use crate::error::{Error, ErrorType, Result};
+use pest::{
+    iterators::{Pair, Pairs},
+    Parser,
+};
+use std::{
+    borrow::Borrow,
+    sync::atomic::{AtomicUsize, Ordering},
 };
-use pest::{iterators::Pair, Parser};
-use std::borrow::Borrow;
 
 #[derive(Parser)]
 #[grammar = "grammar.pest"]
@@ -12,16 +14,27 @@ pub struct CParser;
 
 #[derive(Debug)]
 pub struct Ast {
+    /// Unique among all nodes, used to attach the results of semantic analysis.
+    pub id: usize,
     pub position: (usize, usize),
     pub info: ASTInfo,
 }
 
+/// type, name, position: a parameter of function
+pub type Param = (Type, String, (usize, usize));
+/// type, name, position: a field of struct
+pub type Field = (Type, String, (usize, usize));
+
 #[derive(Debug)]
 pub enum ASTInfo {
     /// type, name, params, block_statements: type name(params) {statements}
-    FunctionDec(Type, String, Vec<(Type, String)>, Box<Ast>),
-    /// type, name
-    VariableDec(Type, String),
+    FunctionDec(Type, String, Vec<Param>, Box<Ast>),
+    /// type, name, params: type name(params);
+    FunctionProto(Type, String, Vec<Param>),
+    /// name, fields: struct name {fields};
+    StructDec(String, Vec<Field>),
+    /// type, name, initializer: type name = initializer
+    VariableDec(Type, String, Option<Box<Ast>>),
 
     /// variable_declarations, expressions
     BlockStmt(Vec<Ast>, Vec<Ast>),
@@ -29,19 +42,48 @@ pub enum ASTInfo {
     SelectionStmt(Box<Ast>, Box<Ast>, Option<Box<Ast>>),
     /// condition, expressions: while(condition) {expression}
     IterationStmt(Box<Ast>, Box<Ast>),
+    /// expressions, condition: do {expression} while(condition)
+    DoWhileStmt(Box<Ast>, Box<Ast>),
+    /// init, condition, step, expressions: for(init; condition; step) {expression}
+    ForStmt(
+        Option<Box<Ast>>,
+        Option<Box<Ast>>,
+        Option<Box<Ast>>,
+        Box<Ast>,
+    ),
+    /// condition, cases: switch(condition) {cases}
+    SwitchStmt(Box<Ast>, Vec<Ast>),
+    /// label, statements: case label: statements, the label of `default` is None
+    CaseStmt(Option<Box<Ast>>, Vec<Ast>),
     /// return value
     ReturnStmt(Option<Box<Ast>>),
+    /// break;
+    BreakStmt,
+    /// continue;
+    ContinueStmt,
 
-    /// var, expression
-    AssignmentExpr(Box<Ast>, Box<Ast>),
+    /// operation, lvalue, expression: lvalue operation= expression, the operation of `=` is None
+    AssignmentExpr(Option<Operand>, Box<Ast>, Box<Ast>),
+    /// operation, expression: operation expression
+    UnaryExpr(UnaryOp, Box<Ast>),
     /// operation, expression, expression: expression operation expression
     BinaryExpr(Operand, Box<Ast>, Box<Ast>),
     /// name, args
     CallExpr(String, Vec<Ast>),
+    /// condition, expression, expression: condition ? expression : expression
+    ConditionalExpr(Box<Ast>, Box<Ast>, Box<Ast>),
 
-    /// name, []: name[]
-    Variable(String, Option<Box<Ast>>),
+    /// name, indices: name[index]...
+    Variable(String, Vec<Ast>),
+    /// struct, member, indices: struct.member[index]...
+    MemberExpr(Box<Ast>, String, Vec<Ast>),
     IntLiteral(i32),
+    /// value of the character, e.g. `'a'`, `'\n'`
+    CharLiteral(u8),
+    /// characters of the string without the terminating `'\0'`
+    StringLiteral(Vec<u8>),
+    /// elements: {elements}
+    InitList(Vec<Ast>),
 }
 
 #[derive(Debug)]
@@ -91,63 +133,80 @@ impl ToString for Operand {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug)]
+pub enum UnaryOp {
+    Neg,
+    Pos,
+    Lnot,
+    Bnot,
+    /// address of a lvalue: `&x`
+    Addr,
+    /// the value pointed to: `*p`
+    Deref,
+    /// `++x`
+    PreInc,
+    /// `--x`
+    PreDec,
+    /// `x++`
+    PostInc,
+    /// `x--`
+    PostDec,
+}
+
+impl ToString for UnaryOp {
+    fn to_string(&self) -> String {
+        match self {
+            Self::Neg => "Neg".to_string(),
+            Self::Pos => "Pos".to_string(),
+            Self::Lnot => "Lnot".to_string(),
+            Self::Bnot => "Bnot".to_string(),
+            Self::Addr => "Addr".to_string(),
+            Self::Deref => "Deref".to_string(),
+            Self::PreInc => "PreInc".to_string(),
+            Self::PreDec => "PreDec".to_string(),
+            Self::PostInc => "PostInc".to_string(),
+            Self::PostDec => "PostDec".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Type {
     Int,
+    Char,
     Void,
-    IntArray(usize),
-    IntPtr,
+    /// Element type and size of the array
+    Array(Box<Type>, usize),
+    /// Pointer to the element type, e.g. array parameter
+    Ptr(Box<Type>),
+    /// Name of the struct
+    Struct(String),
 }
 
 impl ToString for Type {
     fn to_string(&self) -> String {
         match self {
             Self::Int => "int".to_string(),
+            Self::Char => "char".to_string(),
             Self::Void => "void".to_string(),
-            Self::IntArray(size) => format!("int array[{}]", size),
-            Self::IntPtr => "int pointer".to_string(),
+            Self::Array(type_, size) => format!("{} array[{}]", type_.to_string(), size),
+            Self::Ptr(type_) => format!("{} pointer", type_.to_string()),
+            Self::Struct(name) => format!("struct {}", name),
         }
     }
 }
 
-impl<'ctx> Type {
-    pub fn to_llvm_basic_type(self, context: &'ctx Context) -> BasicTypeEnum<'ctx> {
-        match self {
-            Type::Int => context.i32_type().as_basic_type_enum(),
-            Type::Void => panic!("Variable have void type"),
-            Type::IntArray(size) => context
-                .i32_type()
-                .array_type(size as u32)
-                .as_basic_type_enum(),
-            Type::IntPtr => context
-                .i32_type()
-                // .array_type(0)
-                .ptr_type(inkwell::AddressSpace::Generic)
-                .as_basic_type_enum(),
-        }
-    }
-
-    pub fn to_llvm_basic_metadata_type(
-        self,
-        context: &'ctx Context,
-    ) -> BasicMetadataTypeEnum<'ctx> {
-        match self {
-            Type::Int => BasicMetadataTypeEnum::IntType(context.i32_type()),
-            Type::Void => panic!("Variable have void type"),
-            Type::IntArray(size) => {
-                BasicMetadataTypeEnum::ArrayType(context.i32_type().array_type(size as u32))
-            }
-            Type::IntPtr => BasicMetadataTypeEnum::PointerType(
-                context.i32_type().ptr_type(inkwell::AddressSpace::Generic),
-            ),
-        }
+impl Type {
+    /// `int` and `char` are integer types.
+    pub fn is_integer(&self) -> bool {
+        matches!(self, Type::Int | Type::Char)
     }
 }
 
 impl Ast {
     /// Turn the source code to AST,
     /// which can be serialized to json.
-    pub fn parse<T>(source_code: T) -> Result<Vec<Self>, Error>
+    pub fn parse<T>(source_code: T) -> Result<Vec<Self>>
     where
         T: Borrow<str>,
     {
@@ -155,7 +214,7 @@ impl Ast {
             Ok(mut root) => {
                 let root = root.next().unwrap();
                 let mut ast = vec![];
-                visit_program(root, &mut ast);
+                visit_program(root, &mut ast)?;
                 Ok(ast)
             }
             Err(e) => Err(e.into()),
@@ -163,131 +222,338 @@ impl Ast {
     }
 
     fn new(position: (usize, usize), info: ASTInfo) -> Self {
-        Self { position, info }
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            position,
+            info,
+        }
+    }
+
+    /// Evaluate a constant expression at compile time.
+    /// Return `None` if the expression is not constant, e.g. it uses a variable.
+    pub fn eval_const(&self) -> Option<i32> {
+        match &self.info {
+            ASTInfo::IntLiteral(value) => Some(*value),
+            ASTInfo::CharLiteral(value) => Some(*value as i32),
+            ASTInfo::UnaryExpr(op, expr) => {
+                let value = expr.eval_const()?;
+                match op {
+                    UnaryOp::Neg => Some(value.wrapping_neg()),
+                    UnaryOp::Pos => Some(value),
+                    UnaryOp::Lnot => Some((value == 0) as i32),
+                    UnaryOp::Bnot => Some(!value),
+                    UnaryOp::Addr
+                    | UnaryOp::Deref
+                    | UnaryOp::PreInc
+                    | UnaryOp::PreDec
+                    | UnaryOp::PostInc
+                    | UnaryOp::PostDec => None,
+                }
+            }
+            ASTInfo::ConditionalExpr(cond, then_expr, else_expr) => {
+                if cond.eval_const()? != 0 {
+                    then_expr.eval_const()
+                } else {
+                    else_expr.eval_const()
+                }
+            }
+            ASTInfo::BinaryExpr(op, lhs, rhs) => {
+                let lhs = lhs.eval_const()?;
+                // 与运行时一样, 逻辑运算是短路的
+                match op {
+                    Operand::Land if lhs == 0 => return Some(0),
+                    Operand::Lor if lhs != 0 => return Some(1),
+                    _ => {}
+                }
+                let rhs = rhs.eval_const()?;
+                Some(match op {
+                    Operand::Add => lhs.wrapping_add(rhs),
+                    Operand::Sub => lhs.wrapping_sub(rhs),
+                    Operand::Mul => lhs.wrapping_mul(rhs),
+                    Operand::Div => lhs.checked_div(rhs)?,
+                    Operand::Mod => lhs.checked_rem(rhs)?,
+                    Operand::Ge => (lhs >= rhs) as i32,
+                    Operand::Le => (lhs <= rhs) as i32,
+                    Operand::Gt => (lhs > rhs) as i32,
+                    Operand::Lt => (lhs < rhs) as i32,
+                    Operand::Eq => (lhs == rhs) as i32,
+                    Operand::Ne => (lhs != rhs) as i32,
+                    Operand::Band => lhs & rhs,
+                    Operand::Bor => lhs | rhs,
+                    Operand::Bxor => lhs ^ rhs,
+                    Operand::Land | Operand::Lor => (rhs != 0) as i32,
+                    Operand::LShift => lhs.checked_shl(rhs as u32)?,
+                    Operand::RShift => lhs.checked_shr(rhs as u32)?,
+                })
+            }
+            _ => None,
+        }
     }
 }
 
-fn visit_program(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
+fn visit_program(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) -> Result<()> {
     assert_eq!(pair.as_rule(), Rule::program);
     for node in pair.into_inner() {
         match node.as_rule() {
             Rule::func_declaration => {
-                visit_func_declaration(node, ast);
+                visit_func_declaration(node, ast)?;
             }
-            Rule::var_declaration => visit_var_declaration(node, ast),
+            Rule::struct_declaration => visit_struct_declaration(node, ast)?,
+            Rule::var_declaration => visit_var_declaration(node, ast)?,
             Rule::EOI => {}
             _ => unreachable!(),
         }
     }
+    Ok(())
 }
 
-fn visit_func_declaration(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
+fn visit_func_declaration(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) -> Result<()> {
     let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
     let type_spec = visit_type_spec(children.next().unwrap());
     let id = visit_id(children.next().unwrap());
-    let params = visit_params(children.next().unwrap());
-    let block_stmt = visit_block_stmt(children.next().unwrap());
+    let params = visit_params(children.next().unwrap())?;
+    // 没有函数体的是函数原型
+    let info = match children.next() {
+        Some(block_stmt) => {
+            let block_stmt = visit_block_stmt(block_stmt)?;
+            ASTInfo::FunctionDec(type_spec, id, params, Box::new(block_stmt))
+        }
+        None => ASTInfo::FunctionProto(type_spec, id, params),
+    };
+    ast.push(Ast::new(position, info));
+    Ok(())
+}
 
-    ast.push(Ast::new(
-        position,
-        ASTInfo::FunctionDec(type_spec, id, params, Box::new(block_stmt)),
-    ));
+fn visit_struct_declaration(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) -> Result<()> {
+    let position = pair.as_span().start_pos().line_col();
+    let mut children = pair.into_inner();
+    let name = visit_struct_type(children.next().unwrap());
+    let mut fields = vec![];
+    for child in children {
+        let position = child.as_span().start_pos().line_col();
+        let mut children = child.into_inner();
+        let type_spec = visit_type_spec(children.next().unwrap());
+        let id = visit_id(children.next().unwrap());
+        fields.push((
+            visit_array_type(position, type_spec, children)?,
+            id,
+            position,
+        ));
+    }
+    ast.push(Ast::new(position, ASTInfo::StructDec(name, fields)));
+    Ok(())
 }
 
-fn visit_var_declaration(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
+fn visit_var_declaration(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) -> Result<()> {
     let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
     let mut type_spec = visit_type_spec(children.next().unwrap());
     let id = visit_id(children.next().unwrap());
+    let mut array_sizes = vec![];
+    let mut initializer = None;
 
     for child in children {
         match child.as_rule() {
-            Rule::int_literal => {
-                let size = visit_int_literal(child) as usize;
-                type_spec = Type::IntArray(size);
-            }
+            Rule::array_size => array_sizes.push(visit_array_size(child)?),
+            Rule::assign_simple => {}
+            Rule::init_list => initializer = Some(Box::new(visit_init_list(child)?)),
+            Rule::expression => initializer = Some(Box::new(visit_expression(child)?)),
             _ => unreachable!(),
         }
     }
-    ast.push(Ast::new(position, ASTInfo::VariableDec(type_spec, id)));
+    // int a[M][N]是元素类型为int[N]的数组, 所以从最后一维开始构造类型
+    for (dimension, size) in array_sizes.into_iter().enumerate().rev() {
+        // 只有第一维的长度可以省略, 由初始化列表决定
+        let size = match (size, dimension, initializer.as_deref()) {
+            (Some(size), _, _) => size,
+            (
+                None,
+                0,
+                Some(Ast {
+                    info: ASTInfo::InitList(elements),
+                    ..
+                }),
+            ) => elements.len(),
+            _ => Err(Error::new(position, ErrorType::ArraySizeMissing))?,
+        };
+        type_spec = Type::Array(Box::new(type_spec), size);
+    }
+    ast.push(Ast::new(
+        position,
+        ASTInfo::VariableDec(type_spec, id, initializer),
+    ));
+    Ok(())
+}
+
+/// The array type whose sizes of all dimensions are given, e.g. `int a[2][3]`.
+fn visit_array_type(
+    position: (usize, usize),
+    mut type_spec: Type,
+    array_sizes: Pairs<'_, Rule>,
+) -> Result<Type> {
+    let mut sizes = vec![];
+    for array_size in array_sizes {
+        let size = visit_array_size(array_size)?
+            .ok_or_else(|| Error::new(position, ErrorType::ArraySizeMissing))?;
+        sizes.push(size);
+    }
+    for size in sizes.into_iter().rev() {
+        type_spec = Type::Array(Box::new(type_spec), size);
+    }
+    Ok(type_spec)
+}
+
+/// The size of one dimension of an array, `None` if it's omitted: `[]`.
+fn visit_array_size(pair: Pair<'_, Rule>) -> Result<Option<usize>> {
+    match pair.into_inner().next() {
+        Some(size) => Ok(Some(visit_int_literal(size)? as usize)),
+        None => Ok(None),
+    }
+}
+
+fn visit_init_list(pair: Pair<'_, Rule>) -> Result<Ast> {
+    let position = pair.as_span().start_pos().line_col();
+    let mut elements = vec![];
+    for node in pair.into_inner() {
+        match node.as_rule() {
+            Rule::init_list => elements.push(visit_init_list(node)?),
+            Rule::expression => elements.push(visit_expression(node)?),
+            _ => unreachable!(),
+        }
+    }
+    Ok(Ast::new(position, ASTInfo::InitList(elements)))
 }
 
-fn visit_int_literal(pair: Pair<'_, Rule>) -> i32 {
+fn visit_int_literal(pair: Pair<'_, Rule>) -> Result<i32> {
+    let position = pair.as_span().start_pos().line_col();
+    let value = visit_int_literal_value(pair)?;
+    i32::try_from(value).map_err(|_| Error::new(position, ErrorType::IntegerLiteralOverflow))
+}
+
+/// The value of an integer literal without sign,
+/// which may be out of the range of `i32` before negation, e.g. `-2147483648`.
+fn visit_int_literal_value(pair: Pair<'_, Rule>) -> Result<i64> {
+    let position = pair.as_span().start_pos().line_col();
     let child = pair.into_inner().next().unwrap();
-    match child.as_rule() {
-        Rule::bin_literal => i32::from_str_radix(child.as_str(), 2).unwrap(),
-        Rule::oct_literal => i32::from_str_radix(child.as_str(), 8).unwrap(),
-        Rule::dec_literal => i32::from_str_radix(child.as_str(), 10).unwrap(),
-        Rule::hex_literal => i32::from_str_radix(child.as_str(), 16).unwrap(),
+    // 除十进制外, 字面量带有0b/0o/0x前缀
+    let (digits, radix) = match child.as_rule() {
+        Rule::bin_literal => (&child.as_str()[2..], 2),
+        Rule::oct_literal => (&child.as_str()[2..], 8),
+        Rule::dec_literal => (child.as_str(), 10),
+        Rule::hex_literal => (&child.as_str()[2..], 16),
         _ => unreachable!(),
+    };
+    i64::from_str_radix(digits, radix)
+        .map_err(|_| Error::new(position, ErrorType::IntegerLiteralOverflow))
+}
+
+fn visit_char_literal(pair: Pair<'_, Rule>) -> u8 {
+    let content = pair.into_inner().next().unwrap();
+    unescape(content.as_str())[0]
+}
+
+fn visit_string_literal(pair: Pair<'_, Rule>) -> Vec<u8> {
+    let content = pair.into_inner().next().unwrap();
+    unescape(content.as_str())
+}
+
+/// Translate escape sequences such as `\n` to the characters they stand for.
+fn unescape(s: &str) -> Vec<u8> {
+    let mut bytes = vec![];
+    let mut chars = s.bytes();
+    while let Some(c) = chars.next() {
+        if c != b'\\' {
+            bytes.push(c);
+            continue;
+        }
+        // 语法保证转义字符合法
+        bytes.push(match chars.next().unwrap() {
+            b'n' => b'\n',
+            b't' => b'\t',
+            b'r' => b'\r',
+            b'0' => b'\0',
+            c => c,
+        });
     }
+    bytes
 }
+
 fn visit_type_spec(pair: Pair<'_, Rule>) -> Type {
-    let child = pair.into_inner().next().unwrap();
-    match child.as_rule() {
+    let mut children = pair.into_inner();
+    let child = children.next().unwrap();
+    let mut type_ = match child.as_rule() {
         Rule::int => Type::Int,
+        Rule::char => Type::Char,
         Rule::void => Type::Void,
+        Rule::struct_type => Type::Struct(visit_struct_type(child)),
         _ => unreachable!(),
+    };
+    // 每个*都是一层指针, 例如int **p
+    for _ in children {
+        type_ = Type::Ptr(Box::new(type_));
     }
+    type_
+}
+
+fn visit_struct_type(pair: Pair<'_, Rule>) -> String {
+    visit_id(pair.into_inner().next().unwrap())
 }
 
 fn visit_id(pair: Pair<'_, Rule>) -> String {
     pair.as_str().to_string()
 }
 
-fn visit_params(pair: Pair<'_, Rule>) -> Vec<(Type, String)> {
+fn visit_params(pair: Pair<'_, Rule>) -> Result<Vec<Param>> {
     let mut params = vec![];
     for node in pair.into_inner() {
         if Rule::param == node.as_rule() {
-            params.push(visit_param(node));
+            params.push(visit_param(node)?);
         }
     }
-    params
+    Ok(params)
 }
 
-fn visit_param(pair: Pair<'_, Rule>) -> (Type, String) {
+fn visit_param(pair: Pair<'_, Rule>) -> Result<Param> {
+    let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
     let mut type_spec = visit_type_spec(children.next().unwrap());
     let id = visit_id(children.next().unwrap());
+    // int a[][N]的第一维是指针, 之后的维度必须给出长度
     if let Some(x) = children.next() && x.as_rule() == Rule::pointer {
-        type_spec = Type::IntPtr;
+        type_spec = visit_array_type(position, type_spec, children)?;
+        type_spec = Type::Ptr(Box::new(type_spec));
     }
-    (type_spec, id)
+    Ok((type_spec, id, position))
 }
-fn visit_block_stmt(pair: Pair<'_, Rule>) -> Ast {
+fn visit_block_stmt(pair: Pair<'_, Rule>) -> Result<Ast> {
     let position = pair.as_span().start_pos().line_col();
     let children = pair.into_inner();
     let mut vars = vec![];
     let mut statements = vec![];
     for node in children {
         match node.as_rule() {
-            Rule::var_declaration => visit_var_declaration(node, &mut vars),
-            Rule::statement => visit_statement(node, &mut statements),
+            Rule::var_declaration => visit_var_declaration(node, &mut vars)?,
+            Rule::statement => visit_statement(node, &mut statements)?,
             _ => unreachable!(),
         }
     }
-    Ast::new(position, ASTInfo::BlockStmt(vars, statements))
+    Ok(Ast::new(position, ASTInfo::BlockStmt(vars, statements)))
 }
 
-fn visit_statement(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
+fn visit_statement(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) -> Result<()> {
     let position = pair.as_span().start_pos().line_col();
     let children = pair.into_inner().next().unwrap();
     match children.as_rule() {
         Rule::block_stmt => {
-            ast.push(visit_block_stmt(children));
-        }
-        Rule::expression_stmt => {
-            let children = children.into_inner();
-            for node in children {
-                match node.as_rule() {
-                    Rule::expression => {
-                        ast.push(visit_expression(node));
-                    }
-                    _ => unreachable!(),
-                }
-            }
+            ast.push(visit_block_stmt(children)?);
         }
+        // 空语句当作空的复合语句, 这样循环和if总有语句体
+        Rule::expression_stmt => match children.into_inner().next() {
+            Some(node) => ast.push(visit_expression(node)?),
+            None => ast.push(Ast::new(position, ASTInfo::BlockStmt(vec![], vec![]))),
+        },
         Rule::selection_stmt => {
             let children = children.into_inner();
             let mut is_if = true;
@@ -298,14 +564,14 @@ fn visit_statement(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
             for node in children {
                 match node.as_rule() {
                     Rule::expression => {
-                        condition = Some(Box::new(visit_expression(node)));
+                        condition = Some(Box::new(visit_expression(node)?));
                     }
                     Rule::statement if is_if => {
-                        visit_statement(node, &mut if_statement);
+                        visit_statement(node, &mut if_statement)?;
                         is_if = false;
                     }
                     Rule::statement if !is_if => {
-                        visit_statement(node, &mut else_statement);
+                        visit_statement(node, &mut else_statement)?;
                     }
                     _ => unreachable!(),
                 }
@@ -328,9 +594,9 @@ fn visit_statement(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
 
             for node in children {
                 match node.as_rule() {
-                    Rule::expression => condition = Some(Box::new(visit_expression(node))),
+                    Rule::expression => condition = Some(Box::new(visit_expression(node)?)),
                     Rule::statement => {
-                        visit_statement(node, &mut loop_statement);
+                        visit_statement(node, &mut loop_statement)?;
                     }
                     _ => unreachable!(),
                 }
@@ -342,12 +608,71 @@ fn visit_statement(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
             );
             ast.push(Ast::new(position, statement));
         }
+        Rule::do_while_stmt => {
+            let children = children.into_inner();
+            let mut condition: Option<Box<Ast>> = None;
+            let mut loop_statement: Vec<Ast> = vec![];
+
+            for node in children {
+                match node.as_rule() {
+                    Rule::expression => condition = Some(Box::new(visit_expression(node)?)),
+                    Rule::statement => {
+                        visit_statement(node, &mut loop_statement)?;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            let statement = ASTInfo::DoWhileStmt(
+                Box::new(loop_statement.into_iter().next().unwrap()),
+                condition.unwrap(),
+            );
+            ast.push(Ast::new(position, statement));
+        }
+        Rule::for_stmt => {
+            let children = children.into_inner();
+            let mut init: Option<Box<Ast>> = None;
+            let mut condition: Option<Box<Ast>> = None;
+            let mut step: Option<Box<Ast>> = None;
+            let mut loop_statement: Vec<Ast> = vec![];
+
+            for node in children {
+                match node.as_rule() {
+                    Rule::for_init => init = Some(Box::new(visit_for_clause(node)?)),
+                    Rule::for_cond => condition = Some(Box::new(visit_for_clause(node)?)),
+                    Rule::for_step => step = Some(Box::new(visit_for_clause(node)?)),
+                    Rule::statement => {
+                        visit_statement(node, &mut loop_statement)?;
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            let statement = ASTInfo::ForStmt(
+                init,
+                condition,
+                step,
+                Box::new(loop_statement.into_iter().next().unwrap()),
+            );
+            ast.push(Ast::new(position, statement));
+        }
+        Rule::switch_stmt => {
+            let mut children = children.into_inner();
+            let condition = visit_expression(children.next().unwrap())?;
+            let mut cases = vec![];
+            for node in children {
+                cases.push(visit_case_stmt(node)?);
+            }
+
+            let statement = ASTInfo::SwitchStmt(Box::new(condition), cases);
+            ast.push(Ast::new(position, statement));
+        }
         Rule::return_stmt => {
             let children = children.into_inner();
             let mut expression: Option<Box<Ast>> = None;
             for node in children {
                 match node.as_rule() {
-                    Rule::expression => expression = Some(Box::new(visit_expression(node))),
+                    Rule::expression => expression = Some(Box::new(visit_expression(node)?)),
                     _ => unreachable!(),
                 }
             }
@@ -355,34 +680,127 @@ fn visit_statement(pair: Pair<'_, Rule>, ast: &mut Vec<Ast>) {
             let statement = ASTInfo::ReturnStmt(expression);
             ast.push(Ast::new(position, statement));
         }
+        Rule::break_stmt => ast.push(Ast::new(position, ASTInfo::BreakStmt)),
+        Rule::continue_stmt => ast.push(Ast::new(position, ASTInfo::ContinueStmt)),
         _ => unreachable!(),
     }
+    Ok(())
 }
 
-fn visit_expression(mut pair: Pair<'_, Rule>) -> Ast {
+fn visit_case_stmt(pair: Pair<'_, Rule>) -> Result<Ast> {
+    let position = pair.as_span().start_pos().line_col();
+    let mut children = pair.into_inner();
+    let label = children.next().unwrap();
+    let label = match label.as_rule() {
+        Rule::expression => Some(Box::new(visit_expression(label)?)),
+        Rule::default => None,
+        _ => unreachable!(),
+    };
+    let mut statements = vec![];
+    for node in children {
+        visit_statement(node, &mut statements)?;
+    }
+    Ok(Ast::new(position, ASTInfo::CaseStmt(label, statements)))
+}
+
+fn visit_for_clause(pair: Pair<'_, Rule>) -> Result<Ast> {
+    visit_expression(pair.into_inner().next().unwrap())
+}
+
+fn visit_expression(mut pair: Pair<'_, Rule>) -> Result<Ast> {
     if pair.as_rule() == Rule::expression {
         pair = pair.into_inner().next().unwrap();
     }
     match pair.as_rule() {
         Rule::assignment_expr => visit_assignment_expr(pair),
+        Rule::conditional_expr => visit_conditional_expr(pair),
         Rule::unary_expr => visit_unary_expr(pair),
         _ => visit_binary_expr(pair),
     }
 }
 
-fn visit_unary_expr(pair: Pair<'_, Rule>) -> Ast {
+fn visit_unary_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
     let position = pair.as_span().start_pos().line_col();
-    let child = pair.into_inner().next().unwrap();
+    let mut children = pair.into_inner();
+    let child = children.next().unwrap();
     match child.as_rule() {
+        Rule::op_inc
+        | Rule::op_dec
+        | Rule::op_neg
+        | Rule::op_pos
+        | Rule::op_not
+        | Rule::op_bit_not
+        | Rule::op_addr
+        | Rule::op_deref => {
+            let op = match child.as_rule() {
+                Rule::op_inc => UnaryOp::PreInc,
+                Rule::op_dec => UnaryOp::PreDec,
+                Rule::op_neg => UnaryOp::Neg,
+                Rule::op_pos => UnaryOp::Pos,
+                Rule::op_not => UnaryOp::Lnot,
+                Rule::op_bit_not => UnaryOp::Bnot,
+                Rule::op_addr => UnaryOp::Addr,
+                Rule::op_deref => UnaryOp::Deref,
+                _ => unreachable!(),
+            };
+            let expr = children.next().unwrap();
+            // -2147483648不能先解析2147483648再取负, 所以直接作为负数字面量解析
+            if let UnaryOp::Neg = op && let Some(literal) = as_int_literal(&expr) {
+                let value = -visit_int_literal_value(literal)?;
+                let value = i32::try_from(value)
+                    .map_err(|_| Error::new(position, ErrorType::IntegerLiteralOverflow))?;
+                return Ok(Ast::new(position, ASTInfo::IntLiteral(value)));
+            }
+            let expr = visit_unary_expr(expr)?;
+            Ok(Ast::new(position, ASTInfo::UnaryExpr(op, Box::new(expr))))
+        }
         Rule::var => visit_var(child),
-        Rule::int_literal => Ast::new(position, ASTInfo::IntLiteral(visit_int_literal(child))),
+        Rule::int_literal => Ok(Ast::new(
+            position,
+            ASTInfo::IntLiteral(visit_int_literal(child)?),
+        )),
+        Rule::char_literal => Ok(Ast::new(
+            position,
+            ASTInfo::CharLiteral(visit_char_literal(child)),
+        )),
+        Rule::string_literal => Ok(Ast::new(
+            position,
+            ASTInfo::StringLiteral(visit_string_literal(child)),
+        )),
         Rule::call_expr => visit_call_expr(child),
         Rule::bracket_expr => visit_bracket_expr(child),
+        Rule::postfix_expr => visit_postfix_expr(child),
+        _ => unreachable!(),
+    }
+}
+
+fn visit_postfix_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
+    let position = pair.as_span().start_pos().line_col();
+    let mut children = pair.into_inner();
+    let child = children.next().unwrap();
+    let expr = match child.as_rule() {
+        Rule::var => visit_var(child)?,
+        Rule::bracket_expr => visit_bracket_expr(child)?,
+        _ => unreachable!(),
+    };
+    let op = match children.next().unwrap().as_rule() {
+        Rule::op_inc => UnaryOp::PostInc,
+        Rule::op_dec => UnaryOp::PostDec,
         _ => unreachable!(),
+    };
+    Ok(Ast::new(position, ASTInfo::UnaryExpr(op, Box::new(expr))))
+}
+
+/// If the unary expression is just an integer literal, return the literal.
+fn as_int_literal<'i>(pair: &Pair<'i, Rule>) -> Option<Pair<'i, Rule>> {
+    let mut children = pair.clone().into_inner();
+    match (children.next(), children.next()) {
+        (Some(child), None) if child.as_rule() == Rule::int_literal => Some(child),
+        _ => None,
     }
 }
 
-fn visit_bracket_expr(pair: Pair<'_, Rule>) -> Ast {
+fn visit_bracket_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
     let mut children = pair.into_inner();
     loop {
         let child = children.next().unwrap();
@@ -392,53 +810,98 @@ fn visit_bracket_expr(pair: Pair<'_, Rule>) -> Ast {
     }
 }
 
-fn visit_call_expr(pair: Pair<'_, Rule>) -> Ast {
+fn visit_call_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
     let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
     let id = visit_id(children.next().unwrap());
     let mut args = vec![];
-    visit_args(children.next().unwrap(), &mut args);
-    Ast::new(position, ASTInfo::CallExpr(id, args))
+    visit_args(children.next().unwrap(), &mut args)?;
+    Ok(Ast::new(position, ASTInfo::CallExpr(id, args)))
 }
 
-fn visit_args(pair: Pair<'_, Rule>, args: &mut Vec<Ast>) {
+fn visit_args(pair: Pair<'_, Rule>, args: &mut Vec<Ast>) -> Result<()> {
     let children = pair.into_inner();
     for node in children {
         if node.as_rule() == Rule::expression {
-            args.push(visit_expression(node));
+            args.push(visit_expression(node)?);
         }
     }
+    Ok(())
 }
 
-fn visit_assignment_expr(pair: Pair<'_, Rule>) -> Ast {
+fn visit_assignment_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
     let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
-    let var = visit_var(children.next().unwrap());
-    children.next();
-    let expression = visit_expression(children.next().unwrap());
-    Ast::new(
+    let var = visit_unary_expr(children.next().unwrap())?;
+    let op = match children.next().unwrap().as_rule() {
+        Rule::assign_simple => None,
+        Rule::assign_add => Some(Operand::Add),
+        Rule::assign_sub => Some(Operand::Sub),
+        Rule::assign_mul => Some(Operand::Mul),
+        Rule::assign_div => Some(Operand::Div),
+        Rule::assign_mod => Some(Operand::Mod),
+        Rule::assign_lshift => Some(Operand::LShift),
+        Rule::assign_rshift => Some(Operand::RShift),
+        Rule::assign_bit_and => Some(Operand::Band),
+        Rule::assign_bit_or => Some(Operand::Bor),
+        Rule::assign_bit_xor => Some(Operand::Bxor),
+        _ => unreachable!(),
+    };
+    let expression = visit_expression(children.next().unwrap())?;
+    Ok(Ast::new(
         position,
-        ASTInfo::AssignmentExpr(Box::new(var), Box::new(expression)),
-    )
+        ASTInfo::AssignmentExpr(op, Box::new(var), Box::new(expression)),
+    ))
 }
 
-fn visit_var(pair: Pair<'_, Rule>) -> Ast {
+fn visit_conditional_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
     let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
+    let cond = visit_expression(children.next().unwrap())?;
+    match children.next() {
+        Some(then_expr) => {
+            let then_expr = visit_expression(then_expr)?;
+            let else_expr = visit_expression(children.next().unwrap())?;
+            Ok(Ast::new(
+                position,
+                ASTInfo::ConditionalExpr(Box::new(cond), Box::new(then_expr), Box::new(else_expr)),
+            ))
+        }
+        None => Ok(cond),
+    }
+}
+
+fn visit_var(pair: Pair<'_, Rule>) -> Result<Ast> {
+    let position = pair.as_span().start_pos().line_col();
+    let mut children = pair.into_inner().peekable();
     let id = children.next().unwrap().as_str().to_string();
-    let mut expression = None;
+    let mut indices = vec![];
+    while let Some(node) = children.next_if(|node| node.as_rule() == Rule::expression) {
+        indices.push(visit_expression(node)?);
+    }
+    let mut var = Ast::new(position, ASTInfo::Variable(id, indices));
+
+    // a.b[i].c的每个成员访问都以前面的部分作为结构体
     for node in children {
-        if node.as_rule() == Rule::expression {
-            expression = Some(Box::new(visit_expression(node)));
+        let position = node.as_span().start_pos().line_col();
+        let mut children = node.into_inner();
+        let member = visit_id(children.next().unwrap());
+        let mut indices = vec![];
+        for index in children {
+            indices.push(visit_expression(index)?);
         }
+        var = Ast::new(
+            position,
+            ASTInfo::MemberExpr(Box::new(var), member, indices),
+        );
     }
-    Ast::new(position, ASTInfo::Variable(id, expression))
+    Ok(var)
 }
 
-fn visit_binary_expr(pair: Pair<'_, Rule>) -> Ast {
+fn visit_binary_expr(pair: Pair<'_, Rule>) -> Result<Ast> {
     let position = pair.as_span().start_pos().line_col();
     let mut children = pair.into_inner();
-    let mut lhs = visit_expression(children.next().unwrap());
+    let mut lhs = visit_expression(children.next().unwrap())?;
 
     while let Some(mut expr) = children.next() {
         let op = match expr.as_rule() {
@@ -463,13 +926,13 @@ fn visit_binary_expr(pair: Pair<'_, Rule>) -> Ast {
             _ => unreachable!(),
         };
         expr = children.next().unwrap();
-        let rhs = visit_expression(expr);
+        let rhs = visit_expression(expr)?;
         lhs = Ast::new(
             position,
             ASTInfo::BinaryExpr(op, Box::new(lhs), Box::new(rhs)),
         );
     }
-    lhs
+    Ok(lhs)
 }
 
 #[cfg(test)]
@@ -535,4 +998,60 @@ mod test_parse {
             println!("{:?}", i);
         }
     }
+    #[test]
+    fn int_literal_test() {
+        use super::{ASTInfo, Ast};
+
+        fn return_value(literal: &str) -> Option<i32> {
+            let ast = Ast::parse(format!("int main(){{ return {}; }}", literal)).ok()?;
+            if let ASTInfo::FunctionDec(_, _, _, box body) = &ast[0].info
+                && let ASTInfo::BlockStmt(_, statements) = &body.info
+                && let ASTInfo::ReturnStmt(Some(box value)) = &statements[0].info
+                && let ASTInfo::IntLiteral(value) = value.info
+            {
+                return Some(value);
+            }
+            unreachable!()
+        }
+
+        assert_eq!(return_value("0"), Some(0));
+        assert_eq!(return_value("2147483647"), Some(i32::MAX));
+        assert_eq!(return_value("-2147483648"), Some(i32::MIN));
+        assert_eq!(return_value("0b101"), Some(5));
+        assert_eq!(return_value("0o17"), Some(15));
+        assert_eq!(return_value("0x7fffffff"), Some(i32::MAX));
+        assert_eq!(return_value("2147483648"), None);
+        assert_eq!(return_value("-2147483649"), None);
+        assert_eq!(return_value("0x80000000"), None);
+        assert_eq!(return_value("4294967296"), None);
+        assert_eq!(return_value("99999999999999999999999"), None);
+    }
+
+    #[test]
+    fn eval_const_test() {
+        use super::{ASTInfo, Ast};
+
+        fn eval(expr: &str) -> Option<i32> {
+            let ast = Ast::parse(format!("int a = {};", expr)).unwrap();
+            if let ASTInfo::VariableDec(_, _, Some(box init)) = &ast[0].info {
+                return init.eval_const();
+            }
+            unreachable!()
+        }
+
+        assert_eq!(eval("1 + 2 * 3"), Some(7));
+        assert_eq!(eval("(1 + 2) * 3"), Some(9));
+        assert_eq!(eval("-7 / 2"), Some(-3));
+        assert_eq!(eval("-7 % 2"), Some(-1));
+        assert_eq!(eval("~0 << 4"), Some(-16));
+        assert_eq!(eval("'a' + 1"), Some(98));
+        assert_eq!(eval("!5 || 2 > 1"), Some(1));
+        assert_eq!(eval("0 && a"), Some(0));
+        assert_eq!(eval("1 ? 2 : 0 ? 3 : 4"), Some(2));
+        assert_eq!(eval("0 ? a : 5"), Some(5));
+        assert_eq!(eval("2147483647 + 1"), Some(i32::MIN));
+        assert_eq!(eval("1 / 0"), None);
+        assert_eq!(eval("a + 1"), None);
+        assert_eq!(eval("input()"), None);
+    }
 }
@@ -0,0 +1,1105 @@
+use crate::error::{Error, ErrorType, Result, Warning, WarningType};
+use crate::flow;
+use crate::parser::{ASTInfo, Ast, Field, Operand, Param, Type, UnaryOp};
+use std::collections::{HashMap, HashSet};
+
+/// The result of semantic analysis, which is used by the code generator.
+pub struct Semantics {
+    /// The type of every expression that is evaluated at run time.
+    /// Expressions are identified by their ids.
+    types: HashMap<usize, Type>,
+    /// Warnings in the order of their positions.
+    warnings: Vec<Warning>,
+}
+
+impl Semantics {
+    /// The type of the expression, an array is converted to the pointer to its first element.
+    pub fn type_of(&self, ast: &Ast) -> &Type {
+        &self.types[&ast.id]
+    }
+
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+}
+
+/// Resolve names and check types of the program, no llvm context is needed.
+/// All errors are reported in the order of their positions.
+pub fn analyze(ast: &[Ast]) -> std::result::Result<Semantics, Vec<Error>> {
+    let mut analyzer = Analyzer::new();
+    analyzer.analyze(ast);
+    if analyzer.errors.is_empty() {
+        let mut warnings = analyzer.warnings;
+        warnings.sort_by_key(|w| w.position());
+        Ok(Semantics {
+            types: analyzer.types,
+            warnings,
+        })
+    } else {
+        let mut errors = analyzer.errors;
+        errors.sort_by_key(|e| e.position());
+        Err(errors)
+    }
+}
+
+/// A local variable or a parameter, and how it's used.
+struct Variable {
+    type_: Type,
+    position: (usize, usize),
+    is_param: bool,
+    /// The value of the variable is used, or its address is taken.
+    read: bool,
+    /// The variable is assigned after its declaration.
+    written: bool,
+}
+
+struct Analyzer {
+    /// Global variables. Map variables' name to it's type.
+    global_variables: HashMap<String, Type>,
+    /// Global functions. Map functions' name to it's type and parameters' type.
+    global_functions: HashMap<String, (Type, Vec<Type>)>,
+    /// Functions declared by prototypes but not defined yet.
+    prototypes: HashSet<String>,
+    /// Functions that have been defined and their positions.
+    defined_functions: Vec<(String, (usize, usize))>,
+    /// Functions that are called somewhere.
+    called_functions: HashSet<String>,
+    /// Struct definitions. Map structs' name to it's fields.
+    struct_types: HashMap<String, Vec<(Type, String)>>,
+    /// Local variables. It represents the nesting of scopes.
+    variables_stack: Vec<HashMap<String, Variable>>,
+    /// Return type of the function that is being analyzed.
+    current_function: Option<Type>,
+    /// Loops and switches that is being analyzed, true for loops that accept `continue`.
+    loop_stack: Vec<bool>,
+    /// The type of every expression.
+    types: HashMap<usize, Type>,
+    /// Errors found so far, the analysis goes on after an error.
+    errors: Vec<Error>,
+    warnings: Vec<Warning>,
+}
+
+impl Analyzer {
+    fn new() -> Self {
+        let mut global_functions = HashMap::new();
+        // 在io.c中实现的函数
+        global_functions.insert("input".to_string(), (Type::Int, vec![]));
+        global_functions.insert("output".to_string(), (Type::Void, vec![Type::Int]));
+        global_functions.insert(
+            "print_string".to_string(),
+            (Type::Void, vec![Type::Ptr(Box::new(Type::Char))]),
+        );
+
+        Self {
+            global_variables: HashMap::new(),
+            global_functions,
+            prototypes: HashSet::new(),
+            defined_functions: Vec::new(),
+            called_functions: HashSet::new(),
+            struct_types: HashMap::new(),
+            variables_stack: Vec::new(),
+            current_function: None,
+            loop_stack: Vec::new(),
+            types: HashMap::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Record the error and go on.
+    fn report(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            self.errors.push(e);
+        }
+    }
+
+    fn analyze(&mut self, ast: &[Ast]) {
+        for i in ast {
+            match &i.info {
+                ASTInfo::FunctionDec(type_, name, params, body) => {
+                    self.analyze_function(i.position, type_, name, params, body)
+                }
+                ASTInfo::FunctionProto(type_, name, params) => {
+                    self.analyze_function_proto(i.position, type_, name, params)
+                }
+                ASTInfo::StructDec(name, fields) => self.analyze_struct(i.position, name, fields),
+                ASTInfo::VariableDec(type_, name, init) => {
+                    let result =
+                        self.analyze_global_variable(i.position, type_, name, init.as_deref());
+                    self.report(result);
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        for (name, position) in &self.defined_functions {
+            if name != "main" && !self.called_functions.contains(name) {
+                self.warnings
+                    .push(Warning::named(*position, WarningType::UnusedFunction, name));
+            }
+        }
+    }
+
+    fn analyze_struct(&mut self, position: (usize, usize), name: &str, fields: &[Field]) {
+        if self.struct_types.contains_key(name) {
+            self.errors
+                .push(Error::new(position, ErrorType::StructRedefinition));
+            return;
+        }
+        // 先声明结构体, 成员可以是指向自身的指针
+        self.struct_types.insert(name.to_string(), vec![]);
+
+        let mut checked_fields = vec![];
+        for (index, (type_, field, field_position)) in fields.iter().enumerate() {
+            if fields[..index].iter().any(|(_, other, _)| other == field) {
+                self.errors
+                    .push(Error::new(*field_position, ErrorType::VariableRedefinition));
+            }
+            let result = self
+                .check_variable_type(*field_position, type_)
+                .and_then(|()| {
+                    // 结构体不能直接包含自身
+                    let mut elem_type = type_;
+                    while let Type::Array(type_, _) = elem_type {
+                        elem_type = type_;
+                    }
+                    if *elem_type == Type::Struct(name.to_string()) {
+                        Err(Error::new(*field_position, ErrorType::StructContainsItself))?
+                    }
+                    Ok(())
+                });
+            let type_ = self.checked_type(result, type_);
+            checked_fields.push((type_, field.clone()));
+        }
+        self.struct_types.insert(name.to_string(), checked_fields);
+    }
+
+    fn analyze_global_variable(
+        &mut self,
+        position: (usize, usize),
+        type_: &Type,
+        name: &str,
+        init: Option<&Ast>,
+    ) -> Result<()> {
+        if self.global_variables.contains_key(name) || self.global_functions.contains_key(name) {
+            Err(Error::new(position, ErrorType::VariableRedefinition))?
+        }
+        let result = self.check_variable_type(position, type_);
+        let checked_type = self.checked_type(result, type_);
+        self.global_variables
+            .insert(name.to_string(), checked_type.clone());
+        if let Some(init) = init && checked_type == *type_ {
+            self.analyze_const_initializer(type_, init)?;
+        }
+        Ok(())
+    }
+
+    /// The initial value of a global variable must be known at compile time.
+    fn analyze_const_initializer(&self, type_: &Type, init: &Ast) -> Result<()> {
+        match (type_, &init.info) {
+            (Type::Array(elem_type, size), ASTInfo::InitList(elements)) => {
+                if elements.len() > *size {
+                    Err(Error::new(init.position, ErrorType::TooManyInitializers))?
+                }
+                for element in elements {
+                    self.analyze_const_initializer(elem_type, element)?;
+                }
+            }
+            (Type::Struct(name), ASTInfo::InitList(elements)) => {
+                let fields = &self.struct_types[name];
+                if elements.len() > fields.len() {
+                    Err(Error::new(init.position, ErrorType::TooManyInitializers))?
+                }
+                for (element, (field_type, _)) in elements.iter().zip(fields) {
+                    self.analyze_const_initializer(field_type, element)?;
+                }
+            }
+            (_, ASTInfo::InitList(_)) => Err(Error::new(init.position, ErrorType::MismatchedType))?,
+            (type_, _) if type_.is_integer() => {
+                if init.eval_const().is_none() {
+                    Err(Error::new(init.position, ErrorType::InitializerNotConstant))?
+                }
+            }
+            (Type::Ptr(_), _) if is_null_pointer(init) => {}
+            _ => Err(Error::new(init.position, ErrorType::MismatchedType))?,
+        }
+        Ok(())
+    }
+
+    fn analyze_function(
+        &mut self,
+        position: (usize, usize),
+        type_: &Type,
+        name: &str,
+        params: &[Param],
+        body: &Ast,
+    ) {
+        let param_types = self.analyze_signature(position, type_, name, params);
+        let mut p = HashMap::new();
+        for ((_, param_name, param_position), param_type) in params.iter().zip(&param_types) {
+            p.insert(
+                param_name.clone(),
+                Variable {
+                    type_: param_type.clone(),
+                    position: *param_position,
+                    is_param: true,
+                    read: false,
+                    written: false,
+                },
+            );
+        }
+        self.defined_functions.push((name.to_string(), position));
+        if self.prototypes.remove(name) {
+            // 之前声明过原型, 定义必须与原型一致
+            if self.global_functions[name] != (type_.clone(), param_types) {
+                self.errors
+                    .push(Error::new(position, ErrorType::MismatchedPrototype));
+            }
+        } else if self.global_functions.contains_key(name) {
+            self.errors
+                .push(Error::new(position, ErrorType::FunctionRedefinition));
+        } else {
+            self.global_functions
+                .insert(name.to_string(), (type_.clone(), param_types));
+        }
+
+        // 函数头有错误时仍然检查函数体
+        self.variables_stack.push(p);
+        self.current_function = Some(type_.clone());
+        self.analyze_block_stmt(body);
+        self.pop_scope();
+        // 非void函数不能执行到函数的结尾
+        if flow::can_complete(body, &mut self.warnings) && *type_ != Type::Void {
+            self.errors
+                .push(Error::new(position, ErrorType::MissingReturn));
+        }
+        flow::check_initialization(params, body, &mut self.warnings);
+    }
+
+    fn analyze_function_proto(
+        &mut self,
+        position: (usize, usize),
+        type_: &Type,
+        name: &str,
+        params: &[Param],
+    ) {
+        let param_types = self.analyze_signature(position, type_, name, params);
+        match self.global_functions.get(name) {
+            Some(signature) => {
+                if *signature != (type_.clone(), param_types) {
+                    self.errors
+                        .push(Error::new(position, ErrorType::MismatchedPrototype));
+                }
+            }
+            None => {
+                self.global_functions
+                    .insert(name.to_string(), (type_.clone(), param_types));
+                self.prototypes.insert(name.to_string());
+            }
+        }
+    }
+
+    /// Check the return type and parameters' type of the function.
+    fn analyze_signature(
+        &mut self,
+        position: (usize, usize),
+        type_: &Type,
+        name: &str,
+        params: &[Param],
+    ) -> Vec<Type> {
+        if self.global_variables.contains_key(name) {
+            self.errors
+                .push(Error::new(position, ErrorType::FunctionRedefinition));
+        }
+        let result = self.check_type(position, type_);
+        self.report(result);
+        let mut param_types = vec![];
+        for (param_type, _, param_position) in params {
+            let result = self.check_variable_type(*param_position, param_type);
+            param_types.push(self.checked_type(result, param_type));
+        }
+        param_types
+    }
+
+    fn analyze_block_stmt(&mut self, ast: &Ast) {
+        if let ASTInfo::BlockStmt(variables, statements) = &ast.info {
+            self.variables_stack.push(HashMap::new());
+            for var in variables {
+                let result = self.analyze_local_variable(var);
+                self.report(result);
+            }
+
+            for stmt in statements {
+                self.analyze_statement(stmt);
+            }
+            self.pop_scope();
+        }
+    }
+
+    /// Leave the scope and warn about the variables that are never read.
+    fn pop_scope(&mut self) {
+        let mut variables: Vec<_> = self.variables_stack.pop().unwrap().into_iter().collect();
+        variables.sort_by_key(|(_, variable)| variable.position);
+        for (name, variable) in variables {
+            let warning = match variable {
+                Variable { read: true, .. } => continue,
+                Variable { is_param: true, .. } => WarningType::UnusedParameter,
+                Variable { written: true, .. } => WarningType::UnusedButSetVariable,
+                _ => WarningType::UnusedVariable,
+            };
+            self.warnings
+                .push(Warning::named(variable.position, warning, &name));
+        }
+    }
+
+    fn analyze_local_variable(&mut self, var: &Ast) -> Result<()> {
+        if let ASTInfo::VariableDec(type_, name, init) = &var.info {
+            if self.variables_stack.last().unwrap().contains_key(name) {
+                Err(Error::new(var.position, ErrorType::VariableRedefinition))?
+            }
+            let result = self.check_variable_type(var.position, type_);
+            let checked_type = self.checked_type(result, type_);
+            self.variables_stack.last_mut().unwrap().insert(
+                name.clone(),
+                Variable {
+                    type_: checked_type.clone(),
+                    position: var.position,
+                    is_param: false,
+                    read: false,
+                    written: false,
+                },
+            );
+            if let Some(init) = init && checked_type == *type_ {
+                self.analyze_local_initializer(type_, init)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The initial value of a local variable, an initializer list is checked element by element.
+    fn analyze_local_initializer(&mut self, type_: &Type, init: &Ast) -> Result<()> {
+        match (type_, &init.info) {
+            (Type::Array(elem_type, size), ASTInfo::InitList(elements)) => {
+                if elements.len() > *size {
+                    Err(Error::new(init.position, ErrorType::TooManyInitializers))?
+                }
+                for element in elements {
+                    self.analyze_local_initializer(elem_type, element)?;
+                }
+            }
+            (Type::Struct(name), ASTInfo::InitList(elements)) => {
+                let fields = self.struct_types[name].clone();
+                if elements.len() > fields.len() {
+                    Err(Error::new(init.position, ErrorType::TooManyInitializers))?
+                }
+                for (element, (field_type, _)) in elements.iter().zip(&fields) {
+                    self.analyze_local_initializer(field_type, element)?;
+                }
+            }
+            (_, ASTInfo::InitList(_)) => Err(Error::new(init.position, ErrorType::MismatchedType))?,
+            _ => {
+                let init_type = self.analyze_expression(init)?;
+                if !can_assign_value(init, &init_type, type_) {
+                    Err(Error::new(init.position, ErrorType::MismatchedType))?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn analyze_statement(&mut self, stmt: &Ast) {
+        let result = self.check_statement(stmt);
+        self.report(result);
+    }
+
+    /// Nested statements are analyzed even if there are errors in the statement.
+    fn check_statement(&mut self, stmt: &Ast) -> Result<()> {
+        match &stmt.info {
+            ASTInfo::BlockStmt(_, _) => self.analyze_block_stmt(stmt),
+            ASTInfo::SelectionStmt(cond, then_stmt, else_stmt) => {
+                let result = self.analyze_condition(cond);
+                self.report(result);
+                self.analyze_statement(then_stmt);
+                if let Some(else_stmt) = else_stmt {
+                    self.analyze_statement(else_stmt);
+                }
+            }
+            ASTInfo::IterationStmt(cond, loop_stmt) => {
+                let result = self.analyze_condition(cond);
+                self.report(result);
+                self.analyze_loop_body(loop_stmt);
+            }
+            ASTInfo::DoWhileStmt(loop_stmt, cond) => {
+                self.analyze_loop_body(loop_stmt);
+                self.analyze_condition(cond)?;
+            }
+            ASTInfo::ForStmt(init, cond, step, loop_stmt) => {
+                if let Some(init) = init {
+                    self.analyze_statement(init);
+                }
+                if let Some(cond) = cond {
+                    let result = self.analyze_condition(cond);
+                    self.report(result);
+                }
+                self.analyze_loop_body(loop_stmt);
+                if let Some(step) = step {
+                    self.analyze_statement(step);
+                }
+            }
+            ASTInfo::SwitchStmt(cond, cases) => self.analyze_switch_stmt(cond, cases),
+            ASTInfo::ReturnStmt(ret_value) => {
+                let func_return_type = self.current_function.clone().unwrap();
+                match ret_value {
+                    Some(ast) => {
+                        let type_ = self.analyze_expression(ast)?;
+                        if !can_assign_value(ast, &type_, &func_return_type) {
+                            Err(Error::new(ast.position, ErrorType::MismatchedTypeFunction))?
+                        }
+                    }
+                    None => {
+                        if func_return_type != Type::Void {
+                            Err(Error::new(stmt.position, ErrorType::MismatchedTypeFunction))?
+                        }
+                    }
+                }
+            }
+            ASTInfo::BreakStmt => {
+                if self.loop_stack.is_empty() {
+                    Err(Error::new(stmt.position, ErrorType::BreakOutsideLoop))?
+                }
+            }
+            ASTInfo::ContinueStmt => {
+                // switch中的continue属于外层的循环
+                if !self.loop_stack.iter().any(|is_loop| *is_loop) {
+                    Err(Error::new(stmt.position, ErrorType::ContinueOutsideLoop))?
+                }
+            }
+            // 作为语句的函数调用可以返回void
+            ASTInfo::CallExpr(name, arguments) => {
+                let type_ = self.analyze_function_call(stmt.position, name, arguments)?;
+                self.types.insert(stmt.id, type_);
+            }
+            _ => {
+                self.analyze_expression(stmt)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn analyze_loop_body(&mut self, loop_stmt: &Ast) {
+        self.loop_stack.push(true);
+        self.analyze_statement(loop_stmt);
+        self.loop_stack.pop();
+    }
+
+    fn analyze_switch_stmt(&mut self, cond: &Ast, cases: &[Ast]) {
+        match self.analyze_expression(cond) {
+            Ok(type_) if !type_.is_integer() => self
+                .errors
+                .push(Error::new(cond.position, ErrorType::MismatchedType)),
+            Ok(_) => (),
+            Err(e) => self.errors.push(e),
+        }
+
+        let mut labels = vec![];
+        let mut has_default = false;
+        for case in cases {
+            match &case.info {
+                ASTInfo::CaseStmt(Some(label), _) => match label.eval_const() {
+                    Some(label_value) if labels.contains(&label_value) => self
+                        .errors
+                        .push(Error::new(label.position, ErrorType::DuplicateCaseLabel)),
+                    Some(label_value) => labels.push(label_value),
+                    None => self
+                        .errors
+                        .push(Error::new(label.position, ErrorType::CaseLabelNotConstant)),
+                },
+                ASTInfo::CaseStmt(None, _) => {
+                    if has_default {
+                        self.errors
+                            .push(Error::new(case.position, ErrorType::DuplicateCaseLabel));
+                    }
+                    has_default = true;
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        self.loop_stack.push(false);
+        for case in cases {
+            if let ASTInfo::CaseStmt(_, statements) = &case.info {
+                for stmt in statements {
+                    self.analyze_statement(stmt);
+                }
+            }
+        }
+        self.loop_stack.pop();
+    }
+
+    /// Compute the type of the expression and record it.
+    fn analyze_expression(&mut self, ast: &Ast) -> Result<Type> {
+        let type_ = match &ast.info {
+            ASTInfo::AssignmentExpr(op, var, expr) => {
+                self.analyze_assignment_expr(op.as_ref(), var, expr)?
+            }
+            ASTInfo::UnaryExpr(op, expr) => self.analyze_unary_expr(op, expr)?,
+            ASTInfo::BinaryExpr(op, lhs, rhs) => self.analyze_binary_expr(op, lhs, rhs)?,
+            ASTInfo::ConditionalExpr(cond, then_expr, else_expr) => {
+                self.analyze_condition(cond)?;
+                let then_type = promote(self.analyze_expression(then_expr)?);
+                let else_type = promote(self.analyze_expression(else_expr)?);
+                if then_type != else_type {
+                    Err(Error::new(ast.position, ErrorType::MismatchedType))?
+                }
+                then_type
+            }
+            ASTInfo::CallExpr(name, arguments) => {
+                // 在expression上下文中不应该返回void
+                let type_ = self.analyze_function_call(ast.position, name, arguments)?;
+                if type_ == Type::Void {
+                    Err(Error::new(ast.position, ErrorType::ExpressionVoidType))?
+                }
+                type_
+            }
+            ASTInfo::Variable(_, _) | ASTInfo::MemberExpr(_, _, _) => {
+                let type_ = self.analyze_lvalue(ast, true)?;
+                decay(type_)
+            }
+            ASTInfo::IntLiteral(_) | ASTInfo::CharLiteral(_) => Type::Int,
+            ASTInfo::StringLiteral(_) => Type::Ptr(Box::new(Type::Char)),
+            _ => unreachable!(),
+        };
+        self.types.insert(ast.id, type_.clone());
+        Ok(type_)
+    }
+
+    fn analyze_assignment_expr(
+        &mut self,
+        op: Option<&Operand>,
+        var: &Ast,
+        expr: &Ast,
+    ) -> Result<Type> {
+        let type_left = self.analyze_assignable(var)?;
+        let type_right = self.analyze_expression(expr)?;
+        let assignable = match op {
+            Some(op) => {
+                let type_right =
+                    binary_type(op, var.position, decay(type_left.clone()), type_right)?;
+                can_assign(&type_right, &type_left)
+            }
+            None => can_assign_value(expr, &type_right, &type_left),
+        };
+        if !assignable {
+            Err(Error::new(var.position, ErrorType::MismatchedType))?
+        }
+        Ok(type_left)
+    }
+
+    fn analyze_unary_expr(&mut self, op: &UnaryOp, expr: &Ast) -> Result<Type> {
+        match op {
+            UnaryOp::Addr => Ok(Type::Ptr(Box::new(self.analyze_lvalue(expr, true)?))),
+            UnaryOp::Deref => Ok(decay(self.analyze_deref(expr)?)),
+            UnaryOp::PreInc | UnaryOp::PreDec | UnaryOp::PostInc | UnaryOp::PostDec => {
+                // x++就是x += 1, 只是值不同
+                let type_ = self.analyze_assignable(expr)?;
+                let new_type = binary_type(&Operand::Add, expr.position, type_.clone(), Type::Int)?;
+                if !can_assign(&new_type, &type_) {
+                    Err(Error::new(expr.position, ErrorType::MismatchedType))?
+                }
+                Ok(type_)
+            }
+            UnaryOp::Lnot => {
+                self.analyze_condition(expr)?;
+                Ok(Type::Int)
+            }
+            UnaryOp::Neg | UnaryOp::Pos | UnaryOp::Bnot => {
+                if !self.analyze_expression(expr)?.is_integer() {
+                    Err(Error::new(expr.position, ErrorType::MismatchedType))?
+                }
+                Ok(Type::Int)
+            }
+        }
+    }
+
+    fn analyze_binary_expr(&mut self, op: &Operand, left: &Ast, right: &Ast) -> Result<Type> {
+        if let Operand::Land | Operand::Lor = op {
+            self.analyze_condition(left)?;
+            self.analyze_condition(right)?;
+            return Ok(Type::Int);
+        }
+        let lhs_type = self.analyze_expression(left)?;
+        let rhs_type = self.analyze_expression(right)?;
+        // 指针可以与空指针常量比较是否相等
+        if let Operand::Eq | Operand::Ne = op
+            && (matches!(lhs_type, Type::Ptr(_)) && is_null_pointer(right)
+                || is_null_pointer(left) && matches!(rhs_type, Type::Ptr(_)))
+        {
+            return Ok(Type::Int);
+        }
+        binary_type(op, left.position, lhs_type, rhs_type)
+    }
+
+    fn analyze_function_call(
+        &mut self,
+        position: (usize, usize),
+        name: &str,
+        arguments: &[Ast],
+    ) -> Result<Type> {
+        let (type_, param_types) = match self.global_functions.get(name).cloned() {
+            Some(signature) => {
+                self.called_functions.insert(name.to_string());
+                signature
+            }
+            None => {
+                // 未定义的函数当作返回int处理, 继续检查实参
+                self.errors
+                    .push(Error::new(position, ErrorType::FunctionNotDefined));
+                for argument in arguments {
+                    self.analyze_expression(argument)?;
+                }
+                return Ok(Type::Int);
+            }
+        };
+        // 实参多于形参时指向第一个多余的实参, 少于形参时指向函数调用
+        if arguments.len() != param_types.len() {
+            let position = arguments
+                .get(param_types.len())
+                .map_or(position, |argument| argument.position);
+            self.errors
+                .push(Error::new(position, ErrorType::ArgumentCountMismatch));
+        }
+        // 多余的实参没有对应的形参, 但也要检查实参本身
+        for (index, argument) in arguments.iter().enumerate() {
+            let arg_type = self.analyze_expression(argument)?;
+            if let Some(param_type) = param_types.get(index) && !can_assign_value(argument, &arg_type, param_type) {
+                self.errors.push(Error::new(
+                    argument.position,
+                    ErrorType::ArgumentTypeMismatch,
+                ));
+            }
+        }
+        Ok(type_)
+    }
+
+    /// The lvalue that can be assigned, which is not an array.
+    fn analyze_assignable(&mut self, ast: &Ast) -> Result<Type> {
+        match self.analyze_lvalue(ast, false)? {
+            Type::Array(_, _) => Err(Error::new(ast.position, ErrorType::NotLvalue))?,
+            type_ => Ok(type_),
+        }
+    }
+
+    /// The type of a lvalue, which is a variable, a member of struct or `*p`.
+    /// The variable is marked as read or written.
+    fn analyze_lvalue(&mut self, ast: &Ast, read: bool) -> Result<Type> {
+        match &ast.info {
+            ASTInfo::Variable(name, indices) => {
+                let type_ = self.get_name_type(ast.position, name, read);
+                // p[i] = x也读取了指针p的值
+                if !read
+                    && !indices.is_empty()
+                    && let Type::Ptr(_) = type_
+                {
+                    self.get_name_type(ast.position, name, true);
+                }
+                self.analyze_indices(ast.position, type_, indices)
+            }
+            ASTInfo::MemberExpr(base, member, indices) => {
+                let type_ = match self.analyze_lvalue(base, read)? {
+                    Type::Struct(name) => self.struct_types[&name]
+                        .iter()
+                        .find(|(_, field)| field == member)
+                        .map(|(type_, _)| type_.clone())
+                        .ok_or_else(|| Error::new(ast.position, ErrorType::UnknownMember))?,
+                    _ => Err(Error::new(ast.position, ErrorType::MismatchedType))?,
+                };
+                self.analyze_indices(ast.position, type_, indices)
+            }
+            ASTInfo::UnaryExpr(UnaryOp::Deref, expr) => self.analyze_deref(expr),
+            _ => Err(Error::new(ast.position, ErrorType::NotLvalue))?,
+        }
+    }
+
+    /// The type that the pointer expression of `*p` points to.
+    fn analyze_deref(&mut self, expr: &Ast) -> Result<Type> {
+        match self.analyze_expression(expr)? {
+            Type::Ptr(box Type::Void) => {
+                Err(Error::new(expr.position, ErrorType::ExpressionVoidType))?
+            }
+            Type::Ptr(type_) => Ok(*type_),
+            _ => Err(Error::new(expr.position, ErrorType::MismatchedType))?,
+        }
+    }
+
+    /// Every index selects an element of the array or the pointer.
+    fn analyze_indices(
+        &mut self,
+        position: (usize, usize),
+        mut type_: Type,
+        indices: &[Ast],
+    ) -> Result<Type> {
+        for index in indices {
+            if !self.analyze_expression(index)?.is_integer() {
+                Err(Error::new(position, ErrorType::IndexNotInt))?
+            }
+            type_ = match type_ {
+                // 与*p一样, void指针指向的元素不能使用
+                Type::Ptr(box Type::Void) => {
+                    Err(Error::new(position, ErrorType::ExpressionVoidType))?
+                }
+                Type::Ptr(elem_type) | Type::Array(elem_type, _) => *elem_type,
+                _ => Err(Error::new(position, ErrorType::NotSubscriptable))?,
+            };
+        }
+        Ok(type_)
+    }
+
+    /// A condition is an integer or a pointer.
+    fn analyze_condition(&mut self, ast: &Ast) -> Result<()> {
+        match self.analyze_expression(ast)? {
+            Type::Int | Type::Char | Type::Ptr(_) => Ok(()),
+            _ => Err(Error::new(ast.position, ErrorType::MismatchedType))?,
+        }
+    }
+
+    /// An undefined variable is treated as `int`, so that the following code can be checked.
+    /// It's reported only at the first use in a function.
+    fn get_name_type(&mut self, position: (usize, usize), name: &str, read: bool) -> Type {
+        for domain in self.variables_stack.iter_mut().rev() {
+            if let Some(variable) = domain.get_mut(name) {
+                if read {
+                    variable.read = true;
+                } else {
+                    variable.written = true;
+                }
+                return variable.type_.clone();
+            }
+        }
+        if let Some(type_) = self.global_variables.get(name) {
+            return type_.clone();
+        }
+        self.errors
+            .push(Error::new(position, ErrorType::VariableNotDefined));
+        // 加入函数最外层的作用域, 之后的使用不再报告, 也不会产生未使用的警告
+        if let Some(scope) = self.variables_stack.first_mut() {
+            scope.insert(
+                name.to_string(),
+                Variable {
+                    type_: Type::Int,
+                    position,
+                    is_param: false,
+                    read: true,
+                    written: true,
+                },
+            );
+        }
+        Type::Int
+    }
+
+    /// The declared type if it's valid, otherwise the error is recorded and `int` is used instead.
+    fn checked_type(&mut self, result: Result<()>, type_: &Type) -> Type {
+        match result {
+            Ok(()) => type_.clone(),
+            Err(e) => {
+                self.errors.push(e);
+                Type::Int
+            }
+        }
+    }
+
+    /// Variables, parameters and fields can't have void type.
+    fn check_variable_type(&self, position: (usize, usize), type_: &Type) -> Result<()> {
+        if *type_ == Type::Void {
+            Err(Error::new(position, ErrorType::VoidVariable))?
+        }
+        self.check_type(position, type_)
+    }
+
+    /// Check that the structs used by the type have been defined.
+    fn check_type(&self, position: (usize, usize), type_: &Type) -> Result<()> {
+        match type_ {
+            Type::Array(box Type::Void, _) => Err(Error::new(position, ErrorType::VoidVariable))?,
+            Type::Array(type_, _) | Type::Ptr(type_) => self.check_type(position, type_),
+            Type::Struct(name) if !self.struct_types.contains_key(name) => {
+                Err(Error::new(position, ErrorType::StructNotDefined))?
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// An array is converted to the pointer to its first element when it's used as a value.
+fn decay(type_: Type) -> Type {
+    match type_ {
+        Type::Array(elem_type, _) => Type::Ptr(elem_type),
+        type_ => type_,
+    }
+}
+
+/// Integer promotion: `char` is converted to `int` before arithmetic.
+fn promote(type_: Type) -> Type {
+    if type_.is_integer() {
+        Type::Int
+    } else {
+        type_
+    }
+}
+
+/// Integer types are converted to each other for assignment, other types must be the same.
+fn can_assign(from: &Type, to: &Type) -> bool {
+    from == to || (from.is_integer() && to.is_integer())
+}
+
+/// An integer constant expression with value 0 is the null pointer constant.
+fn is_null_pointer(ast: &Ast) -> bool {
+    ast.eval_const() == Some(0)
+}
+
+/// Whether the value of the expression can be assigned, and `0` can be assigned to any pointer.
+fn can_assign_value(value: &Ast, from: &Type, to: &Type) -> bool {
+    can_assign(from, to) || (matches!(to, Type::Ptr(_)) && is_null_pointer(value))
+}
+
+/// The type of the binary operation. Pointers can be added to or subtracted by integers,
+/// and two pointers of the same type can be subtracted or compared.
+fn binary_type(op: &Operand, position: (usize, usize), lhs: Type, rhs: Type) -> Result<Type> {
+    match (op, &lhs, &rhs) {
+        (Operand::Add | Operand::Sub, Type::Ptr(_), index) if index.is_integer() => Ok(lhs),
+        (Operand::Add, index, Type::Ptr(_)) if index.is_integer() => Ok(rhs),
+        (
+            Operand::Sub
+            | Operand::Eq
+            | Operand::Ne
+            | Operand::Lt
+            | Operand::Le
+            | Operand::Gt
+            | Operand::Ge,
+            Type::Ptr(_),
+            Type::Ptr(_),
+        ) if lhs == rhs => Ok(Type::Int),
+        (_, lhs, rhs) if lhs.is_integer() && rhs.is_integer() => Ok(Type::Int),
+        _ => Err(Error::new(position, ErrorType::MismatchedType))?,
+    }
+}
+
+#[cfg(test)]
+mod test_sema {
+    use super::analyze;
+    use crate::{parser::Ast, test_util::for_each_source};
+    use std::path::Path;
+
+    fn sema_ok_test(ok_path: &Path) {
+        for_each_source(ok_path, |ast| {
+            analyze(&ast).unwrap();
+        });
+    }
+
+    #[test]
+    fn sema_test() {
+        sema_ok_test(Path::new("test/algorithm/"));
+        sema_ok_test(Path::new("test/ok/"));
+        sema_ok_test(Path::new("test/with_output/"));
+    }
+
+    #[test]
+    fn warning_test() {
+        let ast = Ast::parse(
+            "int unused(int a, int b) { return a; }
+            void fill(int a[], int n) {
+                int i;
+                for (i = 0; i < n; i++) a[i] = i;
+            }
+            int main() {
+                int x;
+                int y;
+                int z;
+                int p;
+                int w;
+                int arr[2];
+                int *q;
+                y = 1;
+                *&p = 2;
+                z += 1;
+                q = &w;
+                q[0] = 1;
+                fill(arr, 2);
+                return 0;
+            }"
+            .to_string(),
+        )
+        .unwrap();
+        let semantics = analyze(&ast).unwrap();
+        let warnings: Vec<_> = semantics.warnings().iter().map(|w| w.to_string()).collect();
+        assert_eq!(
+            warnings,
+            vec![
+                "1:1: Function `unused` is never called [-Wunused-function]",
+                "1:19: Unused parameter `b` [-Wunused-parameter]",
+                "7:17: Unused variable `x` [-Wunused-variable]",
+                "8:17: Variable `y` is written but never read [-Wunused-but-set-variable]",
+                "9:17: Variable `z` is written but never read [-Wunused-but-set-variable]",
+                "16:17: Variable `z` may be used before it is initialized [-Wuninitialized]",
+            ]
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        use crate::error::Error;
+
+        fn errors(source: &str) -> Vec<String> {
+            let ast = Ast::parse(source).unwrap();
+            match analyze(&ast) {
+                Ok(_) => vec![],
+                Err(errors) => errors.iter().map(Error::to_string).collect(),
+            }
+        }
+
+        // 多余的实参也要检查
+        assert_eq!(
+            errors(
+                "int add(int a, int b) { return a + b; }
+                int main() { return add(1, 2, zz); }"
+            ),
+            vec![
+                "2:47: Number of arguments does not match the function's parameters",
+                "2:47: Variable has not been defined",
+            ]
+        );
+        // void指针不能取下标
+        assert_eq!(
+            errors("int main() { void *vp; vp[0]; return 0; }"),
+            vec!["1:24: Expression has void type"]
+        );
+        // 未定义的变量只在第一次使用时报告
+        assert_eq!(
+            errors("int main() { int x; x = yy + yy; return yy; }"),
+            vec!["1:25: Variable has not been defined"]
+        );
+        // 成员的错误在成员的位置报告
+        assert_eq!(
+            errors(
+                "struct s {
+                    int a;
+                    struct s inner;
+                    void v;
+                    int a;
+                };
+                int main() { return 0; }"
+            ),
+            vec![
+                "3:21: Struct contains itself",
+                "4:21: Variable has void type",
+                "5:21: Variable redefinition",
+            ]
+        );
+    }
+
+    #[test]
+    fn flow_test() {
+        use crate::error::WarningType;
+
+        // 是否通过分析, 以及不可达语句的警告数量
+        fn check(body: &str) -> (bool, usize) {
+            let source = format!("int f(int x) {{ {} }} int main() {{ return f(0); }}", body);
+            let ast = Ast::parse(source).unwrap();
+            match analyze(&ast) {
+                Ok(semantics) => (
+                    true,
+                    semantics
+                        .warnings()
+                        .iter()
+                        .filter(|w| w.warning_type() == WarningType::UnreachableCode)
+                        .count(),
+                ),
+                Err(_) => (false, 0),
+            }
+        }
+
+        assert_eq!(check("return x;"), (true, 0));
+        assert_eq!(check("if (x) return 1;"), (false, 0));
+        assert_eq!(check("if (x) return 1; else return 2;"), (true, 0));
+        assert_eq!(check("while (1) { x = x + 1; }"), (true, 0));
+        assert_eq!(check("while (1) { if (x) break; }"), (false, 0));
+        assert_eq!(check("while (x) { return 1; }"), (false, 0));
+        assert_eq!(check("for (;;) { if (x) return 1; }"), (true, 0));
+        assert_eq!(check("do { return 1; } while (x);"), (true, 0));
+        assert_eq!(check("do { continue; } while (x);"), (false, 0));
+        // 不可达的break和continue不会结束循环
+        assert_eq!(check("while (1) { return 1; break; }"), (true, 1));
+        assert_eq!(check("do { return 1; continue; } while (x);"), (true, 1));
+        assert_eq!(
+            check("switch (x) { case 1: return 1; default: return 2; }"),
+            (true, 0)
+        );
+        assert_eq!(check("switch (x) { case 1: return 1; }"), (false, 0));
+        assert_eq!(
+            check("switch (x) { case 1: break; default: return 2; }"),
+            (false, 0)
+        );
+        assert_eq!(check("return 1; x = 2; x = 3;"), (true, 1));
+        assert_eq!(
+            check("while (x) { break; x = 1; } return 0; output(x);"),
+            (true, 2)
+        );
+    }
+
+    #[test]
+    fn initialization_test() {
+        use crate::error::WarningType;
+
+        // 可能未初始化就读取的位置
+        fn check(body: &str) -> Vec<(usize, usize)> {
+            let source = format!(
+                "int f(int x) {{\n{}\n}}\nint main() {{ return f(0); }}",
+                body
+            );
+            let ast = Ast::parse(source).unwrap();
+            analyze(&ast)
+                .unwrap()
+                .warnings()
+                .iter()
+                .filter(|w| w.warning_type() == WarningType::Uninitialized)
+                .map(|w| w.position())
+                .collect()
+        }
+
+        assert_eq!(check("int a; return a;"), vec![(2, 15)]);
+        assert_eq!(check("int a = 1; return a + x;"), vec![]);
+        assert_eq!(check("int a; a = x; return a;"), vec![]);
+        assert_eq!(check("int a; a += 1; return a;"), vec![(2, 8)]);
+        assert_eq!(check("int a; if (x) a = 1; return a;"), vec![(2, 29)]);
+        assert_eq!(check("int a; if (x) a = 1; else a = 2; return a;"), vec![]);
+        assert_eq!(
+            check("int a; if (x) return 0; else a = 2; return a;"),
+            vec![]
+        );
+        assert_eq!(
+            check("int a; while (x) { a = 1; x--; } return a;"),
+            vec![(2, 41)]
+        );
+        assert_eq!(
+            check("int a; while (1) { a = 1; break; } return a;"),
+            vec![]
+        );
+        assert_eq!(check("int a; do { a = x; } while (a); return a;"), vec![]);
+        assert_eq!(
+            check("int a; for (;;) { if (x) { a = 1; break; } } return a;"),
+            vec![]
+        );
+        assert_eq!(check("int a; int *p; p = &a; *p = 1; return a;"), vec![]);
+        assert_eq!(check("int a; x && (a = 1); return a;"), vec![(2, 29)]);
+        assert_eq!(
+            check("int a; switch (x) { case 1: a = 1; break; default: a = 2; } return a;"),
+            vec![]
+        );
+        assert_eq!(
+            check("int a; switch (x) { case 1: a = 1; break; } return a;"),
+            vec![(2, 52)]
+        );
+        assert_eq!(
+            check("int a[2]; int b; a[0] = b; return a[b];"),
+            vec![(2, 25)]
+        );
+    }
+}
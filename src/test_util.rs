@@ -0,0 +1,26 @@
+//! Helpers shared by the tests of different passes.
+use crate::parser::Ast;
+use std::{
+    fs::{self, File},
+    io::Read,
+    os::unix::prelude::OsStringExt,
+    path::Path,
+};
+
+/// Parse every `.c` file in the directory and pass its ast to `f`.
+pub(crate) fn for_each_source<F>(path: &Path, mut f: F)
+where
+    F: FnMut(Vec<Ast>),
+{
+    for source in fs::read_dir(path).unwrap() {
+        let source = source.unwrap();
+        if source.file_type().unwrap().is_file() && source.file_name().into_vec().ends_with(b".c") {
+            let mut file = File::open(source.path()).unwrap();
+
+            println!("Test source code file {:?}", source);
+            let mut buf = String::new();
+            file.read_to_string(&mut buf).unwrap();
+            f(Ast::parse(buf).unwrap());
+        }
+    }
+}
@@ -0,0 +1,11 @@
+int primes[5] = {2, 3, 5, 7, 11};
+int powers[] = {1, 2, 4, 8, 1 << 4};
+char name[] = {'c', 'm', 'm', '\0'};
+int zeros[3] = {};
+int partial[4] = {-1, 'a'};
+int *pointers[2] = {};
+
+int main(){
+    int empty[2] = {};
+    return 0;
+}
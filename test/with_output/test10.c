@@ -0,0 +1,53 @@
+int sum_digits(int n){
+    int sum;
+    sum = 0;
+    do {
+        sum = sum + n % 10;
+        n = n / 10;
+    } while(n != 0);
+    return sum;
+}
+
+int first_odd(int n){
+    /* the body may end with return */
+    do {
+        if(n % 2 == 1)
+            return n;
+        n = n + 1;
+    } while(n < 100);
+    return 0;
+}
+
+int main(){
+    int i;
+    /*
+        the body runs once even if the condition is false
+        100
+    */
+    i = 100;
+    do output(i); while(i < 10);
+    /*
+        0 has one digit
+        0
+        15
+    */
+    output(sum_digits(0));
+    output(sum_digits(12345));
+    /*
+        9
+    */
+    output(first_odd(8));
+    /*
+        continue jumps to the condition
+        2
+        4
+    */
+    i = 0;
+    do {
+        i = i + 1;
+        if(i % 2 == 1)
+            continue;
+        output(i);
+    } while(i < 4);
+    return 0;
+}
@@ -0,0 +1,57 @@
+int neg(int x){
+    return -x;
+}
+
+int main(){
+    int a;
+    int b;
+    a = 5;
+    b = 0;
+    /*
+        -5
+        5
+        5
+    */
+    output(-a);
+    output(-(-a));
+    output(+a);
+    /*
+        0
+        1
+        1
+        0
+    */
+    output(!a);
+    output(!b);
+    output(!!a);
+    output(!!b);
+    /*
+        -6
+        -1
+    */
+    output(~a);
+    output(~b);
+    /*
+        unary operators bind tighter than binary ones
+        -10
+        -3
+        7
+    */
+    output(-a * 2);
+    output(-a + 2);
+    output(2 - -a);
+    /*
+        stacked prefixes
+        -6
+        5
+    */
+    output(-~a);
+    output(neg(-a));
+    /*
+        1
+    */
+    if(!b && !(a == 4)){
+        output(1);
+    }
+    return 0;
+}
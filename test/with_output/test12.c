@@ -0,0 +1,25 @@
+int main(){
+    /*
+        2147483647
+        -2147483648
+        -2147483647
+    */
+    output(2147483647);
+    output(-2147483648);
+    output(-2147483648 + 1);
+    /*
+        5
+        15
+        31
+        31
+    */
+    output(0b101);
+    output(0o17);
+    output(0x1f);
+    output(0X1F);
+    /*
+        -31
+    */
+    output(-0x1f);
+    return 0;
+}
@@ -0,0 +1,60 @@
+int touch(int x){
+    output(x);
+    return x;
+}
+
+int main(){
+    int a[3];
+    int i;
+    /*
+        operands are compared against zero, the result is 0 or 1
+        1
+        1
+        0
+    */
+    output(1 && 2);
+    output(4 || 0);
+    output(0 || 0);
+    /*
+        the right operand is only evaluated when needed
+        0
+        0
+        1
+        1
+        0
+        5
+        1
+    */
+    output(touch(0) && touch(7));
+    output(touch(1) || touch(8));
+    output(touch(0) || touch(5));
+    /*
+        the guard keeps a[i] in bounds
+        3
+    */
+    a[0] = 1;
+    a[1] = 2;
+    a[2] = 3;
+    i = 0;
+    while(i < 3 && a[i] > 0){
+        i = i + 1;
+    }
+    output(i);
+    /*
+        nested short-circuit
+        0
+        2
+        1
+    */
+    if((touch(0) && touch(9)) || touch(2)){
+        output(1);
+    }
+    /*
+        conditions of if/while are true for any nonzero value
+        2
+    */
+    if(2){
+        output(2);
+    }
+    return 0;
+}
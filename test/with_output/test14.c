@@ -0,0 +1,72 @@
+char grade;
+
+char next(char c){
+    return c + 1;
+}
+
+int count(char s[], char c){
+    int i;
+    int n;
+    i = 0;
+    n = 0;
+    while(s[i] != '\0'){
+        if(s[i] == c){
+            n = n + 1;
+        }
+        i = i + 1;
+    }
+    return n;
+}
+
+int main(){
+    char c;
+    char word[6];
+    /*
+        97
+        10
+        0
+        39
+        92
+    */
+    c = 'a';
+    output(c);
+    output('\n');
+    output('\0');
+    output('\'');
+    output('\\');
+    /*
+        integer promotion
+        98
+        25
+        1
+    */
+    output(c + 1);
+    output('z' - c);
+    output(c < 'b');
+    /*
+        int is truncated when assigned to char
+        44
+        -1
+    */
+    c = 300;
+    output(c);
+    c = 255;
+    output(c);
+    /*
+        66
+    */
+    grade = next('A');
+    output(grade);
+    /*
+        char array as a parameter
+        2
+    */
+    word[0] = 'h';
+    word[1] = 'e';
+    word[2] = 'l';
+    word[3] = 'l';
+    word[4] = 'o';
+    word[5] = '\0';
+    output(count(word, 'l'));
+    return 0;
+}
@@ -0,0 +1,49 @@
+int length(char s[]){
+    int n;
+    n = 0;
+    while(s[n] != '\0'){
+        n = n + 1;
+    }
+    return n;
+}
+
+char at(char s[], int i){
+    return s[i];
+}
+
+void label(char name[], int value){
+    print_string(name);
+    print_string(" = ");
+    output(value);
+}
+
+int main(){
+    int i;
+    int sum;
+    /*
+        hello, world
+        tab	"quoted" \back\
+    */
+    print_string("hello, world\n");
+    print_string("tab\t\"quoted\" \\back\\\n");
+    /*
+        sum = 55
+        length = 5
+        empty = 0
+    */
+    sum = 0;
+    for(i = 1; i <= 10; i = i + 1){
+        sum = sum + i;
+    }
+    label("sum", sum);
+    label("length", length("hello"));
+    label("empty", length(""));
+    /*
+        a string literal is a char pointer
+        104
+        111
+    */
+    output(at("hello", 0));
+    output(at("hello", 4));
+    return 0;
+}
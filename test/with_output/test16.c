@@ -0,0 +1,56 @@
+int size = 4 * 8 + 1;
+int mask = ~0 << 4;
+int flag = 3 > 2 && 1 / 1;
+char letter = 'a' + 2;
+int zero;
+
+int sum(int n){
+    int s = 0;
+    int i = 1;
+    while(i <= n){
+        s = s + i;
+        i = i + 1;
+    }
+    return s;
+}
+
+int main(){
+    int a = 10;
+    int b = a * 2 + sum(3);
+    char c = 'x';
+    /*
+        global initializers are folded at compile time
+        33
+        -16
+        1
+        99
+        0
+    */
+    output(size);
+    output(mask);
+    output(flag);
+    output(letter);
+    output(zero);
+    /*
+        local initializers can use any expression
+        10
+        26
+        120
+    */
+    output(a);
+    output(b);
+    output(c);
+    /*
+        initializers run every time the block is entered
+        1
+        1
+        1
+    */
+    while(a > 7){
+        int once = 0;
+        once = once + 1;
+        output(once);
+        a = a - 1;
+    }
+    return 0;
+}
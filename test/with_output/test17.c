@@ -0,0 +1,56 @@
+int sum(int a[], int n){
+    int s = 0;
+    int i;
+    for(i = 0; i < n; i = i + 1){
+        s = s + a[i];
+    }
+    return s;
+}
+
+int main(){
+    int a[5] = {1, 2, 3};
+    int b[] = {10, 20, 30, 40,};
+    char word[] = {'c', 'm', 'm', '\n', '\0'};
+    int x = 7;
+    int c[4] = {x, x * 2, sum(b, 4)};
+    int i;
+    /*
+        remaining elements are zero
+        1
+        2
+        3
+        0
+        0
+    */
+    for(i = 0; i < 5; i = i + 1){
+        output(a[i]);
+    }
+    /*
+        size is taken from the list
+        100
+        cmm
+    */
+    output(sum(b, 4));
+    print_string(word);
+    /*
+        local initializers can use any expression
+        7
+        14
+        100
+        0
+    */
+    for(i = 0; i < 4; i = i + 1){
+        output(c[i]);
+    }
+    /*
+        the array is initialized again each time the block is entered
+        0
+        0
+    */
+    for(i = 0; i < 2; i = i + 1){
+        int t[3] = {1};
+        output(t[2]);
+        t[2] = 5;
+    }
+    return 0;
+}
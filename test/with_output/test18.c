@@ -0,0 +1,56 @@
+int data[6] = {5, 3, 8, 1, 9, 2};
+int squares[5];
+char greeting[] = {'h', 'i', '\n', '\0'};
+
+void sort(int a[], int n){
+    int i;
+    int j;
+    for(i = 0; i < n; i = i + 1){
+        for(j = 0; j + 1 < n - i; j = j + 1){
+            if(a[j] > a[j + 1]){
+                int t = a[j];
+                a[j] = a[j + 1];
+                a[j + 1] = t;
+            }
+        }
+    }
+}
+
+int main(){
+    int i;
+    /*
+        read a global array
+        5
+        2
+    */
+    output(data[0]);
+    output(data[5]);
+    /*
+        assign to a global array, which starts as zero
+        0
+        16
+        4
+    */
+    output(squares[4]);
+    for(i = 0; i < 5; i = i + 1){
+        squares[i] = i * i;
+    }
+    output(squares[4]);
+    output(squares[2]);
+    /*
+        pass a global array to an array parameter
+        1
+        2
+        3
+        5
+        8
+        9
+        hi
+    */
+    sort(data, 6);
+    for(i = 0; i < 6; i = i + 1){
+        output(data[i]);
+    }
+    print_string(greeting);
+    return 0;
+}
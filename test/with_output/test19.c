@@ -0,0 +1,73 @@
+int identity[3][3] = {{1, 0, 0}, {0, 1, 0}, {0, 0, 1}};
+int grid[4][5];
+
+void multiply(int a[][3], int b[][3], int c[][3], int n){
+    int i;
+    int j;
+    int k;
+    for(i = 0; i < n; i = i + 1){
+        for(j = 0; j < 3; j = j + 1){
+            c[i][j] = 0;
+            for(k = 0; k < 3; k = k + 1){
+                c[i][j] = c[i][j] + a[i][k] * b[k][j];
+            }
+        }
+    }
+}
+
+int row_sum(int row[], int n){
+    int s = 0;
+    int i;
+    for(i = 0; i < n; i = i + 1){
+        s = s + row[i];
+    }
+    return s;
+}
+
+int main(){
+    int m[][3] = {{1, 2, 3}, {4, 5, 6}};
+    int p[2][3];
+    int paths[4][4];
+    int i;
+    int j;
+    /*
+        multiply by the identity matrix
+        1
+        5
+        6
+    */
+    multiply(m, identity, p, 2);
+    output(p[0][0]);
+    output(p[1][1]);
+    output(p[1][2]);
+    /*
+        pass a row to an array parameter
+        6
+        15
+    */
+    output(row_sum(m[0], 3));
+    output(row_sum(p[1], 3));
+    /*
+        dynamic programming on a grid: number of paths
+        20
+    */
+    for(i = 0; i < 4; i = i + 1){
+        for(j = 0; j < 4; j = j + 1){
+            if(i == 0 || j == 0){
+                paths[i][j] = 1;
+            } else {
+                paths[i][j] = paths[i - 1][j] + paths[i][j - 1];
+            }
+        }
+    }
+    output(paths[3][3]);
+    /*
+        global two-dimensional array
+        0
+        7
+    */
+    output(grid[3][4]);
+    grid[2][1] = 7;
+    output(grid[2][1]);
+    return 0;
+}
@@ -0,0 +1,96 @@
+int counter;
+
+void swap(int *a, int *b){
+    int t = *a;
+    *a = *b;
+    *b = t;
+}
+
+int *largest(int *p, int n){
+    int *best = p;
+    int *end = p + n;
+    while(p < end){
+        if(*p > *best){
+            best = p;
+        }
+        p = p + 1;
+    }
+    return best;
+}
+
+int length(char *s){
+    char *p = s;
+    while(*p){
+        p = p + 1;
+    }
+    return p - s;
+}
+
+int main(){
+    int x = 1;
+    int y = 2;
+    int a[5] = {4, 9, 2, 7, 5};
+    int m[2][3] = {{1, 2, 3}, {4, 5, 6}};
+    int *p;
+    int **pp;
+    /*
+        swap through pointers
+        2
+        1
+    */
+    swap(&x, &y);
+    output(x);
+    output(y);
+    /*
+        pointer returned from a function
+        9
+        1
+    */
+    p = largest(a, 5);
+    output(*p);
+    output(p - a);
+    /*
+        arithmetic is scaled by the element size
+        7
+        2
+        7
+    */
+    output(*(a + 3));
+    output(*(p + 1));
+    output(p[2]);
+    /*
+        pointer to pointer
+        20
+    */
+    pp = &p;
+    **pp = 20;
+    output(a[1]);
+    /*
+        pointer to a global variable
+        5
+    */
+    p = &counter;
+    *p = 5;
+    output(counter);
+    /*
+        5
+    */
+    output(length("hello"));
+    /*
+        comparison
+        1
+        0
+        1
+        0
+    */
+    output(&a[1] < &a[3]);
+    output(&a[1] == &a[3]);
+    output(a + 2 == &a[2]);
+    output(!p);
+    /*
+        pointer to a row of a two-dimensional array
+        6
+    */
+    output(*(*(m + 1) + 2));
+    return 0;
+}
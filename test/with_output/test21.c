@@ -0,0 +1,127 @@
+struct point {
+    int x;
+    int y;
+};
+
+struct interval {
+    int start;
+    int done;
+    char tag;
+    int marks[4];
+    struct point corner;
+};
+
+struct node {
+    int value;
+    struct node *next;
+};
+
+struct point origin = {3, 4};
+struct point points[3] = {{1, 2}, {5}};
+
+struct point shift(struct point p, int d){
+    p.x = p.x + d;
+    p.y = p.y + d;
+    return p;
+}
+
+void reset(struct point *p){
+    *p = origin;
+}
+
+int sum(struct node *head){
+    struct node current;
+    int total = 0;
+    while(head){
+        current = *head;
+        total = total + current.value;
+        head = current.next;
+    }
+    return total;
+}
+
+int main(){
+    struct interval s = {1, 2, 'c', {7, 8}};
+    struct point q;
+    struct point r[2];
+    struct node a;
+    struct node b;
+    struct node c = {30};
+    int *p;
+    int i;
+    /*
+        global struct with an initializer list
+        3
+        4
+        5
+        0
+    */
+    output(origin.x);
+    output(origin.y);
+    output(points[1].x);
+    output(points[2].y);
+    /*
+        members of a local struct
+        1
+        2
+        99
+        8
+        0
+    */
+    output(s.start);
+    output(s.done);
+    output(s.tag);
+    output(s.marks[1]);
+    output(s.marks[3]);
+    /*
+        nested struct
+        0
+        11
+    */
+    output(s.corner.x);
+    s.corner.y = 11;
+    output(s.corner.y);
+    /*
+        array of structs
+        0
+        2
+        2
+        4
+    */
+    for(i = 0; i < 2; i = i + 1){
+        r[i].x = i * 2;
+        r[i].y = i * 2 + 2;
+    }
+    output(r[0].x);
+    output(r[0].y);
+    output(r[1].x);
+    output(r[1].y);
+    /*
+        struct assignment, parameter and return value
+        13
+        14
+        3
+    */
+    q = shift(origin, 10);
+    output(q.x);
+    output(q.y);
+    reset(&q);
+    output(q.x);
+    /*
+        pointer to a member
+        40
+    */
+    p = &s.marks[2];
+    *p = 40;
+    output(s.marks[2]);
+    /*
+        linked list
+        60
+    */
+    a.value = 10;
+    a.next = &b;
+    b.value = 20;
+    b.next = &c;
+    output(sum(&a));
+    return 0;
+}
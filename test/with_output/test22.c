@@ -0,0 +1,102 @@
+int classify(int n){
+    int kind;
+    switch(n){
+        case 0:
+            kind = 10;
+            break;
+        case 1:
+        case 2:
+            kind = 20;
+            break;
+        case -1:
+            return -10;
+        default:
+            kind = 30;
+    }
+    return kind;
+}
+
+int vowels(char s[]){
+    int i = 0;
+    int count = 0;
+    while(s[i]){
+        switch(s[i]){
+            case 'a':
+            case 'e':
+            case 'i':
+            case 'o':
+            case 'u':
+                count = count + 1;
+        }
+        i = i + 1;
+    }
+    return count;
+}
+
+int main(){
+    int state = 0;
+    int steps = 0;
+    int i;
+    int total = 0;
+    /*
+        fall-through and default
+        10
+        20
+        20
+        -10
+        30
+    */
+    output(classify(0));
+    output(classify(1));
+    output(classify(2));
+    output(classify(-1));
+    output(classify(7));
+    /*
+        5
+    */
+    output(vowels("education"));
+    /*
+        state machine
+        3
+        3
+    */
+    while(state != 3){
+        switch(state){
+            case 0:
+                state = 2;
+                break;
+            case 2:
+                state = 1;
+                break;
+            case 1:
+                state = 3;
+                break;
+        }
+        steps = steps + 1;
+    }
+    output(state);
+    output(steps);
+    /*
+        continue inside a switch goes to the next iteration of the loop
+        8
+    */
+    for(i = 0; i < 6; i = i + 1){
+        switch(i % 3){
+            case 1 + 1:
+                continue;
+            default:
+                total = total + i;
+        }
+    }
+    output(total);
+    /*
+        no case matched without default
+        8
+    */
+    switch(state){
+        case 0:
+            total = 100;
+    }
+    output(total);
+    return 0;
+}
@@ -0,0 +1,57 @@
+int calls;
+int limit = 1 ? 10 : 20;
+
+int touch(int n){
+    calls = calls + 1;
+    return n;
+}
+
+int sign(int n){
+    return n > 0 ? 1 : n < 0 ? -1 : 0;
+}
+
+int main(){
+    int a[3] = {5, 6, 7};
+    int b[3] = {8, 9, 10};
+    int *p;
+    char c = 'x';
+    int i = 2;
+    /*
+        only the selected expression is evaluated
+        3
+        1
+    */
+    output(i > 1 ? touch(3) : touch(4));
+    output(calls);
+    /*
+        right associativity
+        1
+        -1
+        0
+    */
+    output(sign(7));
+    output(sign(-7));
+    output(sign(0));
+    /*
+        pointers and characters
+        9
+        120
+        10
+    */
+    p = i ? b : a;
+    output(p[1]);
+    output(i == 2 ? c : 'y');
+    output(limit);
+    /*
+        nested in a condition, an assignment and a case label
+        4
+        2
+    */
+    i = (i ? 0 : 1) ? 3 : 4;
+    output(i);
+    switch(i){
+        case 1 ? 4 : 0:
+            output(calls + 1);
+    }
+    return 0;
+}
@@ -0,0 +1,100 @@
+int calls;
+
+int next(int i){
+    calls++;
+    return i;
+}
+
+int main(){
+    int a[4] = {1, 2, 3, 4};
+    int i;
+    int j = 5;
+    int x = 100;
+    char c = 'a';
+    int *p = a;
+    int sum = 0;
+    /*
+        compound assignment
+        105
+        95
+        190
+        63
+        3
+        12
+        3
+        2
+        7
+        5
+    */
+    x += j;
+    output(x);
+    x -= 10;
+    output(x);
+    x *= 2;
+    output(x);
+    x /= 3;
+    output(x);
+    x %= 10;
+    output(x);
+    x <<= 2;
+    output(x);
+    x >>= 2;
+    output(x);
+    x &= 6;
+    output(x);
+    x |= 5;
+    output(x);
+    x ^= 2;
+    output(x);
+    /*
+        the index is evaluated once
+        11
+        1
+    */
+    a[next(2)] += 8;
+    output(a[2]);
+    output(calls);
+    /*
+        prefix and postfix
+        5
+        6
+        7
+        7
+        6
+        6
+    */
+    output(j++);
+    output(j);
+    output(++j);
+    output(j--);
+    output(j);
+    output(--j + 1);
+    /*
+        counting loop
+        18
+    */
+    for(i = 0; i < 4; i++){
+        sum += a[i];
+    }
+    output(sum);
+    /*
+        pointers and characters
+        2
+        11
+        98
+    */
+    p++;
+    output(*p);
+    p += 1;
+    output(*p);
+    c++;
+    output(c);
+    /*
+        assignment in a condition
+        0
+    */
+    while(j -= 1){
+    }
+    output(j);
+    return 0;
+}
@@ -0,0 +1,84 @@
+int data[5] = {4, 8, 15, 0, 16};
+int pos;
+
+int next_value(){
+    int value = data[pos];
+    pos++;
+    return value;
+}
+
+struct pair {
+    int first;
+    int second;
+};
+
+int main(){
+    int a;
+    int b;
+    int c;
+    int x;
+    int m[2][3];
+    int *p = &a;
+    char ch;
+    struct pair s;
+    int count = 0;
+    /*
+        chained assignment is right associative
+        7
+        7
+        7
+    */
+    a = b = c = 7;
+    output(a);
+    output(b);
+    output(c);
+    /*
+        the value of an assignment is the value stored
+        44
+        44
+    */
+    x = ch = 300;
+    output(ch);
+    output(x);
+    /*
+        assignment in a condition
+        3
+        27
+    */
+    a = 0;
+    while((x = next_value()) != 0){
+        count++;
+        a += x;
+    }
+    output(count);
+    output(a);
+    /*
+        indexed, member and dereferenced targets
+        9
+        9
+        9
+        9
+    */
+    m[1][2] = s.first = *p = s.second = 9;
+    output(m[1][2]);
+    output(s.first);
+    output(a);
+    output(s.second);
+    /*
+        compound assignment in a chain
+        12
+        3
+    */
+    b = m[0][0] = 3;
+    c = b += m[0][0] * 3;
+    output(c);
+    output(m[0][0]);
+    /*
+        assignment in the condition of if
+        1
+    */
+    if(x = 1){
+        output(x);
+    }
+    return 0;
+}
@@ -0,0 +1,47 @@
+void output(int number);
+int is_even(int n);
+int is_odd(int n);
+int sum(int a[], int n);
+int sum(int values[], int count);
+
+int main(){
+    int a[4] = {1, 2, 3, 4};
+    /*
+        mutual recursion
+        1
+        0
+        1
+    */
+    output(is_even(10));
+    output(is_even(7));
+    output(is_odd(7));
+    /*
+        called before its definition
+        10
+    */
+    output(sum(a, 4));
+    return 0;
+}
+
+int is_even(int n){
+    if(n == 0){
+        return 1;
+    }
+    return is_odd(n - 1);
+}
+
+int is_odd(int n){
+    if(n == 0){
+        return 0;
+    }
+    return is_even(n - 1);
+}
+
+int sum(int values[], int count){
+    int total = 0;
+    int i;
+    for(i = 0; i < count; i++){
+        total += values[i];
+    }
+    return total;
+}
@@ -0,0 +1,74 @@
+int sign(int n){
+    if(n > 0){
+        return 1;
+    } else if(n < 0){
+        return -1;
+    } else {
+        return 0;
+    }
+}
+
+int first_multiple(int n, int k){
+    int i;
+    for(i = n; ; i++){
+        if(i % k == 0){
+            return i;
+        }
+    }
+}
+
+int grade(int score){
+    switch(score / 10){
+        case 10:
+        case 9:
+            return 4;
+        case 8:
+            return 3;
+        case 7:
+        case 6:
+            return 2;
+        default:
+            return 0;
+    }
+}
+
+int count_down(int n){
+    do {
+        if(n <= 0){
+            return n;
+        }
+        n--;
+        continue;
+    } while(1);
+}
+
+int main(){
+    /*
+        every path returns
+        1
+        -1
+        0
+    */
+    output(sign(5));
+    output(sign(-3));
+    output(sign(0));
+    /*
+        infinite loops left by return
+        21
+        0
+    */
+    output(first_multiple(17, 7));
+    output(count_down(3));
+    /*
+        switch with default
+        4
+        3
+        2
+        0
+    */
+    output(grade(95));
+    output(grade(80));
+    output(grade(61));
+    output(grade(12));
+    return 0;
+}
@@ -0,0 +1,21 @@
+int first_zero(int a[], int n){
+    int i;
+    for(i = 0; i < n && a[i] != 0; i++) ;
+    return i;
+}
+
+int main(){
+    int a[5] = {3, 1, 0, 4, 0};
+    int i;
+    /*
+        loops with empty bodies
+        2
+        5
+        10
+    */
+    output(first_zero(a, 5));
+    output(first_zero(a, 2) + 3);
+    for(i = 0; i < 10; i++) ;
+    output(i);
+    return 0;
+}
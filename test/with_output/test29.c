@@ -0,0 +1,24 @@
+int count = 0;
+
+int tick(){
+    count++;
+    return count < 4;
+}
+
+int main(){
+    int n;
+    /*
+        do-while with an empty body
+        4
+    */
+    do ; while(tick());
+    output(count);
+    /*
+        the body runs once even if the condition is false
+        1
+    */
+    n = 0;
+    do ; while(n++ < 0);
+    output(n);
+    return 0;
+}
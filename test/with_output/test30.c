@@ -0,0 +1,60 @@
+struct node {
+    int value;
+    struct node *next;
+};
+
+struct node *empty = 0;
+
+int length(struct node *head){
+    struct node current;
+    int n = 0;
+    while(head != 0){
+        current = *head;
+        n++;
+        head = current.next;
+    }
+    return n;
+}
+
+int *find(int a[], int n, int x){
+    int i;
+    for(i = 0; i < n; i++){
+        if(a[i] == x){
+            return &a[i];
+        }
+    }
+    return 0;
+}
+
+int main(){
+    struct node a = {1, 0};
+    struct node b = {2};
+    struct node c;
+    int numbers[3] = {4, 5, 6};
+    int *p;
+    c.value = 3;
+    c.next = 0;
+    b.next = &c;
+    a.next = &b;
+    /*
+        0 is the null pointer
+        3
+        0
+        1
+    */
+    output(length(&a));
+    output(length(empty));
+    output(empty == 0);
+    /*
+        compare with null
+        1
+        5
+    */
+    p = find(numbers, 3, 7);
+    output(p == 0);
+    p = find(numbers, 3, 5);
+    if(0 != p){
+        output(*p);
+    }
+    return 0;
+}
@@ -0,0 +1,41 @@
+int main(){
+    int i;
+    int j;
+    int sum;
+    /*
+        0
+        1
+        2
+    */
+    for(i = 0; i < 3; i = i + 1){
+        output(i);
+    }
+    /*
+        the init and step can be omitted
+        3
+    */
+    for(; i < 4;){
+        output(i);
+        i = i + 1;
+    }
+    /*
+        nested loops
+        10
+    */
+    sum = 0;
+    for(i = 1; i <= 4; i = i + 1)
+        for(j = 0; j < i; j = j + 1)
+            sum = sum + 1;
+    output(sum);
+    /*
+        a loop without condition only ends by return
+        7
+    */
+    for(i = 0; ; i = i + 1){
+        if(i == 7){
+            output(i);
+            return 0;
+        }
+    }
+    return 0;
+}
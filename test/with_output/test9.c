@@ -0,0 +1,61 @@
+int main(){
+    int i;
+    int j;
+    int found;
+    /*
+        continue skips the rest of the body, but not the step of for loop
+        1
+        3
+        5
+    */
+    for(i = 0; i < 6; i = i + 1){
+        if(i % 2 == 0)
+            continue;
+        output(i);
+    }
+    /*
+        break only leaves the innermost loop
+        3
+        3
+    */
+    found = 0;
+    i = 0;
+    while(i < 3){
+        j = 0;
+        while(1){
+            if(j == 3)
+                break;
+            j = j + 1;
+        }
+        found = found + 1;
+        i = i + 1;
+    }
+    output(j);
+    output(found);
+    /*
+        search the first multiple of 7 greater than 50
+        56
+    */
+    i = 51;
+    while(1){
+        if(i % 7 == 0){
+            break;
+        }
+        i = i + 1;
+    }
+    output(i);
+    /*
+        continue in while loop goes back to the condition
+        4
+    */
+    i = 0;
+    j = 0;
+    while(i < 8){
+        i = i + 1;
+        if(i % 2 == 1)
+            continue;
+        j = j + 1;
+    }
+    output(j);
+    return 0;
+}
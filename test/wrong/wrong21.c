@@ -0,0 +1,7 @@
+int main(){
+    int i;
+    i = 0;
+    if(i == 0)
+        break;
+    return 0;
+}
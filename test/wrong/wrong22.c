@@ -0,0 +1,9 @@
+int main(){
+    int i;
+    i = 0;
+    while(i < 10){
+        i = i + 1;
+    }
+    continue;
+    return 0;
+}
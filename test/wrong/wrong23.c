@@ -0,0 +1,5 @@
+int main(){
+    int a;
+    a = 4294967296;
+    return 0;
+}
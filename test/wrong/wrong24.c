@@ -0,0 +1,5 @@
+int main(){
+    int a;
+    a = 0x80000000;
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main(){
+    char c;
+    c = 'ab';
+    return 0;
+}
@@ -0,0 +1,5 @@
+int a[];
+
+int main(){
+    return 0;
+}
@@ -0,0 +1,6 @@
+int n = 3;
+int a[3] = {1, n};
+
+int main(){
+    return 0;
+}
@@ -0,0 +1,7 @@
+int a[3];
+int b[3];
+
+int main(){
+    a = b;
+    return 0;
+}
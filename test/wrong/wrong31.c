@@ -0,0 +1,5 @@
+int main(){
+    int a;
+    a[0] = 1;
+    return 0;
+}
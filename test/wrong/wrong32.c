@@ -0,0 +1,7 @@
+int sum(int m[][], int n){
+    return 0;
+}
+
+int main(){
+    return 0;
+}
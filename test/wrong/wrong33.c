@@ -0,0 +1,7 @@
+int main(){
+    int a;
+    int *p;
+    p = &a;
+    a = p + 1;
+    return 0;
+}
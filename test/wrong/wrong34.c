@@ -0,0 +1,6 @@
+int main(){
+    int a;
+    int *p;
+    p = a;
+    return 0;
+}
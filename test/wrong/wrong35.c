@@ -0,0 +1,5 @@
+int main(){
+    int a;
+    -a = 2;
+    return 0;
+}
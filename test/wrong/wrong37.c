@@ -0,0 +1,4 @@
+int main(){
+    struct point p;
+    return 0;
+}
@@ -0,0 +1,11 @@
+int main(){
+    int a = 1;
+    switch(a){
+        case 1:
+            a = 2;
+            break;
+        case 2 - 1:
+            a = 3;
+    }
+    return 0;
+}
@@ -0,0 +1,9 @@
+int main(){
+    int a = 1;
+    int b = 2;
+    switch(a){
+        case b:
+            a = 2;
+    }
+    return 0;
+}
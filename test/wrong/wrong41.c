@@ -0,0 +1,6 @@
+int main(){
+    int a[3];
+    int *p;
+    p = a[0] ? a : 0;
+    return 0;
+}
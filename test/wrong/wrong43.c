@@ -0,0 +1,6 @@
+int main(){
+    int a[4];
+    int *p = a;
+    p *= 2;
+    return 0;
+}
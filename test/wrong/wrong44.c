@@ -0,0 +1,9 @@
+int f(int a, int b);
+
+int f(int a, char b){
+    return a + b;
+}
+
+int main(){
+    return f(1, 2);
+}
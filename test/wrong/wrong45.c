@@ -0,0 +1,13 @@
+int f(int a);
+
+int f(int a){
+    return a;
+}
+
+int f(int a){
+    return a + 1;
+}
+
+int main(){
+    return f(1);
+}
@@ -0,0 +1,4 @@
+int main(){
+    output();
+    return 0;
+}
@@ -0,0 +1,8 @@
+int sum(int x[], int n){
+    return x[0] + n;
+}
+
+int main(){
+    int a = 1;
+    return sum(a, 1);
+}
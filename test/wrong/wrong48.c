@@ -0,0 +1,8 @@
+int add(int a, int b){
+    return a + b;
+}
+
+int main(){
+    output(add(1, 2, 3));
+    return 0;
+}
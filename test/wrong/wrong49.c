@@ -0,0 +1,14 @@
+int add(int a, int b) {
+    return a + b;
+}
+
+int main() {
+    int x;
+    x = y + 1;
+    output(add(x));
+    if (z) {
+        break;
+    }
+    output(sub(x, 1));
+    return 0;
+}
@@ -0,0 +1,12 @@
+int magnitude(int n){
+    if(n < 0){
+        return -n;
+    } else if(n > 0){
+        return n;
+    }
+}
+
+int main(){
+    output(magnitude(-1));
+    return 0;
+}
@@ -0,0 +1,7 @@
+int first(void *vp){
+    return vp[0];
+}
+
+int main(){
+    return 0;
+}